@@ -0,0 +1,268 @@
+//! Known-good answers, used to check that the solutions still work.
+//!
+//! The answers are kept in a small TOML file, with one table per day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = "514579"
+//! part2 = "241861950"
+//!
+//! [2021.day03]
+//! part1 = "198"
+//! ```
+//!
+//! Tables without a year are 2020's, which had the file to itself before
+//! there were other years.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::solver::Part;
+
+/// The default location of the answers file.
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// The year of tables named just `[dayNN]`.
+const UNNAMED_YEAR: u32 = 2020;
+
+/// Reads a table name, `dayNN` or `YYYY.dayNN`, into its year and day.
+pub fn parse_table(name: &str) -> Option<(u32, u32)> {
+    let (year, day) = match name.split_once('.') {
+        Some((year, day)) => (year.parse().ok()?, day),
+        None => (UNNAMED_YEAR, name),
+    };
+    Some((year, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// The name of the table for a year's day.
+pub fn table_name(year: u32, day: u32) -> String {
+    match year {
+        UNNAMED_YEAR => format!("day{:02}", day),
+        _ => format!("{}.day{:02}", year, day),
+    }
+}
+
+/// How an answer compares with the known one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// A different answer is known.
+    Fail,
+    /// No answer was known, so this one is now.
+    Recorded,
+    /// No answer is known.
+    Unknown,
+}
+
+impl Verdict {
+    /// The status shown for it by verify.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "FAIL",
+            Verdict::Recorded => "recorded",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    /// By year, day and part.
+    answers: BTreeMap<(u32, u32, u32), String>,
+}
+
+impl Answers {
+    /// Parses the contents of an answers file.
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers = BTreeMap::new();
+        let mut table = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[') {
+                match name.strip_suffix(']').and_then(parse_table) {
+                    Some(year_day) => table = Some(year_day),
+                    None => return Err(format!("line {}: invalid table `{}`", idx + 1, line)),
+                }
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(format!("line {}: expected `key = value`", idx + 1)),
+            };
+            let part = match key.strip_prefix("part").and_then(|n| n.parse::<u32>().ok()) {
+                Some(part) => part,
+                None => return Err(format!("line {}: invalid key `{}`", idx + 1, key)),
+            };
+            let (year, day) = match table {
+                Some(year_day) => year_day,
+                None => {
+                    return Err(format!(
+                        "line {}: answer outside of a [dayNN] table",
+                        idx + 1
+                    ))
+                }
+            };
+
+            // answers are normally quoted, but allow bare numbers too
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            answers.insert((year, day, part), value.to_string());
+        }
+
+        Ok(Answers { answers })
+    }
+
+    /// Loads the answers from a file. A missing file has no answers.
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Answers::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: Part) -> Option<&str> {
+        self.answers
+            .get(&(year, day, part.number()))
+            .map(|answer| answer.as_str())
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, part: Part, answer: String) {
+        self.answers.insert((year, day, part.number()), answer);
+    }
+
+    /// Compares an answer with the known one, recording it if there isn't
+    /// one and `record` is set. Known answers are never replaced.
+    pub fn verify(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: Part,
+        answer: &str,
+        record: bool,
+    ) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None if record => {
+                self.insert(year, day, part, answer.to_string());
+                Verdict::Recorded
+            }
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        let mut current_table = None;
+
+        for ((year, day, part), answer) in &self.answers {
+            if current_table != Some((*year, *day)) {
+                if current_table.is_some() {
+                    toml.push('\n');
+                }
+                toml.push_str(&format!("[{}]\n", table_name(*year, *day)));
+                current_table = Some((*year, *day));
+            }
+            toml.push_str(&format!("part{} = \"{}\"\n", part, answer));
+        }
+
+        toml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(2020, 1, Part::One, "514579".to_string());
+        answers.insert(2020, 1, Part::Two, "241861950".to_string());
+        answers.insert(2021, 3, Part::One, "198".to_string());
+
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        answers.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let loaded = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            text,
+            "[day01]\npart1 = \"514579\"\npart2 = \"241861950\"\n\n[2021.day03]\npart1 = \"198\"\n"
+        );
+        assert_eq!(loaded.answers, answers.answers);
+
+        // a missing file has no answers, rather than being an error
+        assert!(Answers::load(&path).unwrap().answers.is_empty());
+    }
+
+    #[test]
+    fn records_new_days() {
+        let mut answers = Answers::parse("[day01]\npart1 = 514579\n").unwrap();
+        assert_eq!(
+            answers.verify(2020, 2, Part::One, "2", false),
+            Verdict::Unknown
+        );
+        assert_eq!(answers.get(2020, 2, Part::One), None);
+
+        assert_eq!(
+            answers.verify(2020, 2, Part::One, "2", true),
+            Verdict::Recorded
+        );
+        assert_eq!(answers.get(2020, 2, Part::One), Some("2"));
+        assert_eq!(answers.verify(2020, 2, Part::One, "2", true), Verdict::Pass);
+        assert_eq!(answers.get(2020, 1, Part::One), Some("514579"));
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let mut answers = Answers::parse("[day01]\npart1 = \"514579\"\n").unwrap();
+        assert_eq!(
+            answers.verify(2020, 1, Part::One, "514579", false),
+            Verdict::Pass
+        );
+        assert_eq!(answers.verify(2020, 1, Part::One, "1", true), Verdict::Fail);
+        // even when recording, the known answer stays
+        assert_eq!(answers.get(2020, 1, Part::One), Some("514579"));
+        // and the year matters
+        assert_eq!(
+            answers.verify(2021, 1, Part::One, "1", false),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn bad_files() {
+        assert_eq!(
+            Answers::parse("part1 = 1\n").unwrap_err(),
+            "line 1: answer outside of a [dayNN] table"
+        );
+        assert_eq!(
+            Answers::parse("[day01]\n\npart = 1\n").unwrap_err(),
+            "line 3: invalid key `part`"
+        );
+        assert_eq!(
+            Answers::parse("[dayone]\n").unwrap_err(),
+            "line 1: invalid table `[dayone]`"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! Life-like cellular automata: cells are alive or dead, and each generation
+//! a cell's fate depends only on how many of its neighbours are alive.
+//!
+//! What counts as a neighbour is up to the [`Topology`], and when cells are
+//! born or survive is up to the [`Rule`].
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+use crate::grid::{Grid, Offset, Pos, ADJACENT};
+use crate::log;
+
+/// How many live neighbours a cell needs to be born or to stay alive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub birth: RangeInclusive<usize>,
+    pub survival: RangeInclusive<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: RangeInclusive<usize>, survival: RangeInclusive<usize>) -> Rule {
+        Rule { birth, survival }
+    }
+
+    /// Whether a cell is alive in the next generation.
+    pub fn next(&self, alive: bool, live_neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&live_neighbours)
+        } else {
+            self.birth.contains(&live_neighbours)
+        }
+    }
+}
+
+/// The cells of an automaton and which of them are neighbours.
+///
+/// Neighbourhoods must be symmetric: if `a` is a neighbour of `b`, then `b`
+/// is a neighbour of `a`.
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+
+    /// Every cell that can be alive, if there are finitely many. Otherwise only
+    /// the cells next to live ones are considered, so nothing is born with no
+    /// live neighbours.
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+
+    /// Calls `f` with each neighbour of the cell.
+    fn neighbours(&self, cell: Self::Cell, f: impl FnMut(Self::Cell));
+}
+
+/// The infinite lattice of cubes in `N` dimensions, where every cube touching
+/// another (even at a corner) is a neighbour.
+pub struct Lattice<const N: usize> {
+    offsets: Vec<[i64; N]>,
+}
+
+impl<const N: usize> Lattice<N> {
+    pub fn new() -> Lattice<N> {
+        let mut offsets = vec![[0; N]];
+        for dim in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-1..=1).map(move |d| {
+                        let mut offset = offset;
+                        offset[dim] = d;
+                        offset
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|offset| offset.iter().any(|d| *d != 0));
+
+        Lattice { offsets }
+    }
+}
+
+impl<const N: usize> Default for Lattice<N> {
+    fn default() -> Lattice<N> {
+        Lattice::new()
+    }
+}
+
+impl<const N: usize> Topology for Lattice<N> {
+    type Cell = [i64; N];
+
+    fn neighbours(&self, cell: [i64; N], mut f: impl FnMut([i64; N])) {
+        for offset in &self.offsets {
+            let mut neighbour = cell;
+            for (n, d) in neighbour.iter_mut().zip(offset) {
+                *n += d;
+            }
+            f(neighbour);
+        }
+    }
+}
+
+/// The infinite plane of hexagons, in axial coordinates `(x, y)`: `x` goes
+/// east, and `y` goes north-east.
+pub struct Hex;
+
+impl Hex {
+    pub const OFFSETS: [(i32, i32); 6] = [(-1, 0), (1, 0), (0, 1), (0, -1), (1, -1), (-1, 1)];
+}
+
+impl Topology for Hex {
+    type Cell = (i32, i32);
+
+    fn neighbours(&self, (x, y): (i32, i32), mut f: impl FnMut((i32, i32))) {
+        for (dx, dy) in Hex::OFFSETS.iter() {
+            f((x + dx, y + dy));
+        }
+    }
+}
+
+/// Some of the cells of a grid, with what they can see as neighbours. The
+/// other cells are never alive.
+pub struct Bounded {
+    neighbours: Grid<Vec<Pos>>,
+    cells: Vec<Pos>,
+}
+
+impl Bounded {
+    /// The cells that are `true` in the mask, with the 8 cells around each as
+    /// neighbours.
+    pub fn adjacent(mask: &Grid<bool>) -> Bounded {
+        Bounded::new(mask, |pos| {
+            mask.neighbours8(pos)
+                .filter(|neighbour| mask[*neighbour])
+                .collect()
+        })
+    }
+
+    /// The cells that are `true` in the mask, with the first such cell seen
+    /// in each of the 8 directions as neighbours.
+    pub fn line_of_sight(mask: &Grid<bool>) -> Bounded {
+        Bounded::new(mask, |pos| {
+            ADJACENT
+                .iter()
+                .filter_map(|offset: &Offset| mask.cast(pos, *offset, |cell| !cell))
+                .collect()
+        })
+    }
+
+    fn new(mask: &Grid<bool>, neighbours: impl Fn(Pos) -> Vec<Pos>) -> Bounded {
+        Bounded {
+            neighbours: mask.map_with_pos(|pos, cell| match cell {
+                true => neighbours(pos),
+                false => Vec::new(),
+            }),
+            cells: mask
+                .iter()
+                .filter(|(_, cell)| **cell)
+                .map(|(pos, _)| pos)
+                .collect(),
+        }
+    }
+}
+
+impl Topology for Bounded {
+    type Cell = Pos;
+
+    fn cells(&self) -> Option<Vec<Pos>> {
+        Some(self.cells.clone())
+    }
+
+    fn neighbours(&self, pos: Pos, f: impl FnMut(Pos)) {
+        self.neighbours[pos].iter().copied().for_each(f);
+    }
+}
+
+/// The live cells of an automaton, and how they evolve.
+pub struct Automaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    live: HashSet<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> Automaton<T> {
+    pub fn new(topology: T, rule: Rule, live: impl IntoIterator<Item = T::Cell>) -> Automaton<T> {
+        Automaton {
+            topology,
+            rule,
+            live: live.into_iter().collect(),
+            generation: 0,
+        }
+    }
+
+    pub fn live(&self) -> &HashSet<T::Cell> {
+        &self.live
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    /// How many generations have passed.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Moves on a generation, returning whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut counts: HashMap<T::Cell, usize> = HashMap::new();
+        for cell in &self.live {
+            self.topology.neighbours(*cell, |neighbour| {
+                *counts.entry(neighbour).or_default() += 1
+            });
+        }
+
+        let candidates = match self.topology.cells() {
+            Some(cells) => cells,
+            None => self.live.iter().chain(counts.keys()).copied().collect(),
+        };
+
+        let live: HashSet<T::Cell> = candidates
+            .into_iter()
+            .filter(|cell| {
+                let n_live = counts.get(cell).copied().unwrap_or(0);
+                self.rule.next(self.live.contains(cell), n_live)
+            })
+            .collect();
+
+        let changed = live != self.live;
+        self.live = live;
+        self.generation += 1;
+        changed
+    }
+
+    /// Runs for the given number of generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+            log!(
+                "after {} generations: {} alive",
+                self.generation,
+                self.population()
+            );
+        }
+    }
+
+    /// Runs until nothing changes any more, returning how many generations
+    /// changed something. This never returns if the automaton doesn't settle
+    /// down.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut n_changed = 0;
+        while self.step() {
+            n_changed += 1;
+            log!(
+                "after {} generations: {} alive",
+                self.generation,
+                self.population()
+            );
+        }
+
+        n_changed
+    }
+
+    /// Records the live cells of each generation, starting with this one, for
+    /// up to the given number of generations (or forever). Stops early once
+    /// nothing changes any more.
+    pub fn history(&mut self, generations: Option<usize>) -> Vec<HashSet<T::Cell>> {
+        let mut history = vec![self.live.clone()];
+        while generations.is_none_or(|generations| history.len() <= generations) && self.step() {
+            history.push(self.live.clone());
+        }
+
+        history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lattice_neighbours() {
+        let mut n = 0;
+        Lattice::<3>::new().neighbours([0, 0, 0], |_| n += 1);
+        assert_eq!(n, 26);
+    }
+
+    #[test]
+    fn blinker() {
+        let rule = Rule::new(3..=3, 2..=3);
+        let mut life = Automaton::new(Lattice::<2>::new(), rule, vec![[0, -1], [0, 0], [0, 1]]);
+        life.step();
+        let mut live: Vec<_> = life.live().iter().copied().collect();
+        live.sort_unstable();
+        assert_eq!(live, [[-1, 0], [0, 0], [1, 0]]);
+        life.run(3);
+        assert_eq!((life.generation(), life.population()), (4, 3));
+        assert_eq!(life.history(Some(2)).len(), 3);
+    }
+
+    #[test]
+    fn settles_on_bounded_grid() {
+        // everything is born at once, then stays
+        let mask = Grid::new(2, 2, true);
+        let mut automaton = Automaton::new(Bounded::adjacent(&mask), Rule::new(0..=0, 0..=3), None);
+        assert_eq!(automaton.run_until_stable(), 1);
+        assert_eq!(automaton.population(), 4);
+    }
+}
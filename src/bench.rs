@@ -0,0 +1,310 @@
+//! Timing the solutions, and comparing the timings against a baseline.
+//!
+//! Parsing and solving are timed separately. The baseline keeps the median
+//! times of a run in the same kind of TOML file as the answers, in
+//! nanoseconds, and with the `heap-stats` feature the peak heap in bytes:
+//!
+//! ```toml
+//! [day15]
+//! part2.parse_ns = 2100
+//! part2.solve_ns = 1830000000
+//! part2.heap_bytes = 120000000
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solver::{Part, Solution};
+
+/// The default location of the baseline file.
+pub const DEFAULT_BASELINE: &str = "bench.toml";
+
+/// Changes smaller than this are put down to noise.
+pub const NOISE: Duration = Duration::from_micros(100);
+
+/// How long one run of a part took.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+    /// The most heap that parsing and solving had allocated at once, if
+    /// that's counted (see `heap`).
+    pub heap: Option<usize>,
+}
+
+/// Summary of the times of several runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples, or returns `None` if there aren't any.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let mut samples = samples.to_vec();
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = match n {
+            0 => return None,
+            _ if n % 2 == 1 => samples[n / 2],
+            _ => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        };
+
+        Some(Stats {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        })
+    }
+}
+
+/// The timings of the runs of one part.
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub parse: Stats,
+    pub solve: Stats,
+    /// The peak heap of the run that used the most.
+    pub heap: Option<usize>,
+}
+
+impl Measurement {
+    /// The median time of a whole run.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.solve.median
+    }
+}
+
+/// Parses and solves the part `runs` times (at least once).
+pub fn measure(
+    solution: &dyn Solution,
+    input: &str,
+    part: Part,
+    params: &Params,
+    runs: usize,
+) -> Result<Measurement, Error> {
+    let mut parse_times = Vec::new();
+    let mut solve_times = Vec::new();
+    let mut heap = None;
+    for _ in 0..runs.max(1) {
+        let timing = solution.time(input, part, params)?;
+        parse_times.push(timing.parse);
+        solve_times.push(timing.solve);
+        heap = heap.max(timing.heap);
+    }
+
+    Ok(Measurement {
+        parse: Stats::from_samples(&parse_times).unwrap(),
+        solve: Stats::from_samples(&solve_times).unwrap(),
+        heap,
+    })
+}
+
+/// Formats a duration with a sensible unit, e.g. `1.23ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+/// How much slower `new` is than `old`, in percent (negative if faster).
+pub fn change(old: Duration, new: Duration) -> f64 {
+    let old = old.as_nanos() as f64;
+    let new = new.as_nanos() as f64;
+    if old == 0.0 {
+        0.0
+    } else {
+        (new - old) / old * 100.0
+    }
+}
+
+/// Whether `new` is more than `threshold` percent slower than `old`,
+/// ignoring differences within the noise.
+pub fn is_slowdown(old: Duration, new: Duration, threshold: f64) -> bool {
+    new > old + NOISE && change(old, new) > threshold
+}
+
+/// Median times from an earlier run.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    /// By year, day and part.
+    medians: BTreeMap<(u32, u32, u32), Timing>,
+}
+
+impl Baseline {
+    /// Parses the contents of a baseline file.
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let mut medians: BTreeMap<(u32, u32, u32), Timing> = BTreeMap::new();
+        let mut table = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[') {
+                match name.strip_suffix(']').and_then(answers::parse_table) {
+                    Some(year_day) => table = Some(year_day),
+                    None => return Err(format!("line {}: invalid table `{}`", idx + 1, line)),
+                }
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(format!("line {}: expected `key = value`", idx + 1)),
+            };
+            let (part, phase) = match key.split_once('.') {
+                Some((part, phase)) => (part.strip_prefix("part"), phase),
+                None => (None, key),
+            };
+            let part = match part.and_then(|n| n.parse::<u32>().ok()) {
+                Some(part) => part,
+                None => return Err(format!("line {}: invalid key `{}`", idx + 1, key)),
+            };
+            let value = match value.parse::<u64>() {
+                Ok(value) => value,
+                Err(_) => return Err(format!("line {}: invalid number `{}`", idx + 1, value)),
+            };
+            let (year, day) = match table {
+                Some(year_day) => year_day,
+                None => return Err(format!("line {}: key outside of a [dayNN] table", idx + 1)),
+            };
+
+            let timing = medians.entry((year, day, part)).or_default();
+            match phase {
+                "parse_ns" => timing.parse = Duration::from_nanos(value),
+                "solve_ns" => timing.solve = Duration::from_nanos(value),
+                "heap_bytes" => timing.heap = Some(value as usize),
+                _ => return Err(format!("line {}: invalid key `{}`", idx + 1, key)),
+            }
+        }
+
+        Ok(Baseline { medians })
+    }
+
+    /// Loads a baseline from a file. A missing file is an error here, since
+    /// there's nothing to compare against.
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let text = fs::read_to_string(path)?;
+        Baseline::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: Part) -> Option<Timing> {
+        self.medians.get(&(year, day, part.number())).copied()
+    }
+
+    /// Records the medians of a measurement.
+    pub fn insert(&mut self, year: u32, day: u32, part: Part, measurement: &Measurement) {
+        let timing = Timing {
+            parse: measurement.parse.median,
+            solve: measurement.solve.median,
+            heap: measurement.heap,
+        };
+        self.medians.insert((year, day, part.number()), timing);
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        let mut current_table = None;
+
+        for ((year, day, part), timing) in &self.medians {
+            if current_table != Some((*year, *day)) {
+                if current_table.is_some() {
+                    toml.push('\n');
+                }
+                toml.push_str(&format!("[{}]\n", answers::table_name(*year, *day)));
+                current_table = Some((*year, *day));
+            }
+            toml.push_str(&format!(
+                "part{}.parse_ns = {}\n",
+                part,
+                timing.parse.as_nanos()
+            ));
+            toml.push_str(&format!(
+                "part{}.solve_ns = {}\n",
+                part,
+                timing.solve.as_nanos()
+            ));
+            if let Some(heap) = timing.heap {
+                toml.push_str(&format!("part{}.heap_bytes = {}\n", part, heap));
+            }
+        }
+
+        toml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!(stats.median, ms(3));
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn slowdowns() {
+        assert!(is_slowdown(ms(10), ms(12), 10.0));
+        assert!(!is_slowdown(ms(10), ms(10) + NOISE / 2, 0.0));
+        assert!(!is_slowdown(ms(10), ms(8), 10.0));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let text = "[day15]\npart1.parse_ns = 2100\npart1.solve_ns = 450000\n\n\
+                    [2021.day03]\npart2.parse_ns = 10\npart2.solve_ns = 20\n\
+                    part2.heap_bytes = 4096\n";
+        let baseline = Baseline::parse(text).unwrap();
+        assert!(baseline.get(2021, 15, Part::One).is_none());
+        assert_eq!(
+            baseline.get(2021, 3, Part::Two).unwrap().solve.as_nanos(),
+            20
+        );
+        let timing = baseline.get(2020, 15, Part::One).unwrap();
+        assert_eq!(timing.parse, Duration::from_nanos(2100));
+        assert_eq!(timing.solve, Duration::from_micros(450));
+        assert_eq!(timing.heap, None);
+        assert_eq!(baseline.get(2021, 3, Part::Two).unwrap().heap, Some(4096));
+        assert_eq!(baseline.to_toml(), text);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(640)), "640ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_340)), "12.3µs");
+        assert_eq!(format_duration(ms(5)), "5.00ms");
+        assert_eq!(format_duration(ms(1500)), "1.50s");
+    }
+}
@@ -0,0 +1,238 @@
+//! Answers kept on disk, so that a part that's already been solved isn't
+//! solved again.
+//!
+//! An answer is kept for exactly what went into it: the year, day and part,
+//! the version of the day's solver, the values of its parameters and a hash
+//! of the input. Changing any of them means solving again. Each answer is a
+//! small file in the cache directory, with the key it's for, the answer and
+//! how long solving took:
+//!
+//! ```text
+//! key = "2020 day 15 part 2 version 1 turns=30000000 input 5bd3c5bc8ec2f73a"
+//! answer = 175594
+//! time_ns = 1830000000
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::hash::fnv1a;
+use crate::params::Params;
+use crate::solver::{Answer, Part, Solution};
+
+/// Where answers are kept when no directory is given.
+pub const DEFAULT_DIR: &str = "cache";
+
+/// What an answer was worked out from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    year: u32,
+    day: u32,
+    part: Part,
+    /// All of the key, as it's written in the file.
+    text: String,
+}
+
+impl Key {
+    /// The key for solving a part of the input, with the parameters
+    /// resolved the way the solver would resolve them.
+    pub fn new(
+        year: u32,
+        solution: &dyn Solution,
+        part: Part,
+        overrides: &Params,
+        input: &str,
+    ) -> Result<Key, Error> {
+        let day = solution.day();
+        let params = overrides.resolve(day, solution.params(), part)?;
+
+        let mut text = format!(
+            "{} day {} part {} version {}",
+            year,
+            day,
+            part,
+            solution.version()
+        );
+        for (name, value) in params.iter() {
+            text.push_str(&format!(" {}={}", name, value));
+        }
+        text.push_str(&format!(" input {:016x}", fnv1a(input)));
+
+        Ok(Key {
+            year,
+            day,
+            part,
+            text,
+        })
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{}-day{:02}-part{}-{:016x}.answer",
+            self.year,
+            self.day,
+            self.part,
+            fnv1a(&self.text)
+        )
+    }
+}
+
+/// A cached answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub answer: Answer,
+    /// How long solving took when the answer was worked out.
+    pub elapsed: Duration,
+}
+
+/// A directory of cached answers.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Cache {
+        Cache { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The answer for the key, if there is one. Files that can't be read,
+    /// or are for a different key that happens to have the same name, are
+    /// treated as missing.
+    pub fn get(&self, key: &Key) -> Option<Entry> {
+        let text = fs::read_to_string(self.dir.join(key.file_name())).ok()?;
+        let mut lines = text.lines();
+
+        if lines.next()? != format!("key = {:?}", key.text) {
+            return None;
+        }
+        let answer = lines.next()?.strip_prefix("answer = ")?;
+        let answer = match answer.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+            Some(text) => Answer::Text(text.to_string()),
+            None => Answer::Number(answer.parse().ok()?),
+        };
+        let nanos = lines.next()?.strip_prefix("time_ns = ")?.parse().ok()?;
+
+        Some(Entry {
+            answer,
+            elapsed: Duration::from_nanos(nanos),
+        })
+    }
+
+    /// Saves the answer for the key, replacing any other. Answers on more
+    /// than one line aren't kept.
+    pub fn put(&self, key: &Key, entry: &Entry) -> io::Result<()> {
+        let answer = match &entry.answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(text) if text.contains('\n') => return Ok(()),
+            Answer::Text(text) => format!("\"{}\"", text),
+        };
+        fs::create_dir_all(&self.dir)?;
+
+        // write the whole thing somewhere else first, so that another run
+        // reading it at the same time never sees half of it
+        let path = self.dir.join(key.file_name());
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        let mut file = fs::File::create(&partial)?;
+        write!(
+            file,
+            "key = {:?}\nanswer = {}\ntime_ns = {}\n",
+            key.text,
+            answer,
+            entry.elapsed.as_nanos()
+        )?;
+        drop(file);
+        fs::rename(&partial, &path)
+    }
+
+    /// Removes every cached answer, returning how many there were.
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => return Err(error),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("answer") => removed += 1,
+                Some("partial") => (),
+                _ => continue,
+            }
+            fs::remove_file(&path)?;
+        }
+        // only if nothing else was put there
+        let _ = fs::remove_dir(&self.dir);
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::day01::Day1;
+    use crate::y2020::day21::Day21;
+
+    #[test]
+    fn keys() {
+        let key = |params: &[(&str, &str)], input: &str| {
+            let mut overrides = Params::new();
+            for (name, value) in params {
+                overrides.set(name, value);
+            }
+            Key::new(2020, &Day1, Part::One, &overrides, input)
+        };
+
+        let default = key(&[], "1721\n979\n").unwrap();
+        assert_eq!(
+            default.text,
+            format!(
+                "2020 day 1 part 1 version 3 entries=2 total=2020 input {:016x}",
+                fnv1a("1721\n979\n")
+            )
+        );
+        // spelling out the default is the same run
+        assert_eq!(key(&[("total", "2020")], "1721\n979\n").unwrap(), default);
+        assert_ne!(key(&[("total", "10")], "1721\n979\n").unwrap(), default);
+        assert_ne!(key(&[], "1721\n978\n").unwrap(), default);
+        assert!(key(&[("nope", "1")], "").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let cache =
+            Cache::new(std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id())));
+        let key = |solution: &dyn Solution, input: &str| {
+            Key::new(2020, solution, Part::Two, &Params::new(), input).unwrap()
+        };
+
+        let number = Entry {
+            answer: Answer::Number(241861950),
+            elapsed: Duration::from_micros(15),
+        };
+        let text = Entry {
+            answer: Answer::Text("mxmxvkd,sqjhc,fvjkl".to_string()),
+            elapsed: Duration::from_millis(2),
+        };
+        assert_eq!(cache.get(&key(&Day1, "1\n")), None);
+        cache.put(&key(&Day1, "1\n"), &number).unwrap();
+        cache.put(&key(&Day21, "1\n"), &text).unwrap();
+
+        assert_eq!(cache.get(&key(&Day1, "1\n")), Some(number));
+        assert_eq!(cache.get(&key(&Day21, "1\n")), Some(text));
+        assert_eq!(cache.get(&key(&Day1, "2\n")), None);
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert_eq!(cache.get(&key(&Day1, "1\n")), None);
+        assert!(!cache.dir().exists());
+    }
+}
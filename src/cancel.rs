@@ -0,0 +1,76 @@
+//! Stopping solvers whose answers aren't wanted any more, like the server's
+//! after they time out.
+//!
+//! A `Cancel` is handed to the thread doing the solving with `run`, and the
+//! long loops (those that report their `Progress`) stop with
+//! `Error::Cancelled` soon after `cancel` is called. Solvers without a long
+//! loop aren't stopped, and run to the end.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+thread_local! {
+    /// The `Cancel` for whatever the thread is running, if anything can stop it.
+    static CURRENT: RefCell<Option<Cancel>> = const { RefCell::new(None) };
+}
+
+/// A flag for stopping a solver, shared between it and whoever may stop it.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Cancel {
+        Cancel::default()
+    }
+
+    /// Asks the solver to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` on this thread, with its long loops stopping once `cancel`
+    /// is called.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let outer = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let _restore = Restore(outer);
+        f()
+    }
+}
+
+/// Puts back the thread's outer `Cancel` when dropped, even after a panic.
+struct Restore(Option<Cancel>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = outer);
+    }
+}
+
+/// The `Cancel` of what this thread is running, if there is one.
+pub fn current() -> Option<Cancel> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_while_running() {
+        let cancel = Cancel::new();
+        assert!(current().is_none());
+        cancel.run(|| {
+            let inner = current().unwrap();
+            assert!(!inner.is_cancelled());
+            cancel.cancel();
+            assert!(inner.is_cancelled());
+        });
+        assert!(current().is_none());
+    }
+}
@@ -0,0 +1,196 @@
+//! Snapshots of the long-running loops, so that they can carry on where they
+//! left off after being stopped.
+//!
+//! With `--checkpoint <dir>`, a solver that supports it saves its state in
+//! the directory every so often, and picks up from a saved state for the same
+//! run (the same input and parameters) instead of starting over. The snapshot
+//! is removed once the run finishes.
+
+use std::convert::TryInto;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::hash::fnv1a;
+use crate::log;
+
+/// The first line of every checkpoint file.
+const MAGIC: &str = "aoc-2020-rust checkpoint 1";
+
+/// How often `due` looks at the clock, in steps.
+const CHECK_EVERY: usize = 1 << 16;
+
+/// How often checkpoints are saved when no interval is given.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+static SETTINGS: Mutex<Option<(PathBuf, Duration)>> = Mutex::new(None);
+
+/// Turns checkpoints on, saving them in `dir` every `interval`.
+pub fn enable(dir: PathBuf, interval: Duration) {
+    *SETTINGS.lock().unwrap() = Some((dir, interval));
+}
+
+/// State that can be saved in a checkpoint, as a list of numbers.
+pub trait Snapshot: Sized {
+    fn to_words(&self) -> Vec<u64>;
+
+    /// Rebuilds the state, or `None` if the words aren't a valid one.
+    fn from_words(words: &[u64]) -> Option<Self>;
+}
+
+/// The checkpoint of one run.
+pub struct Checkpoint {
+    path: PathBuf,
+    /// What the run is, so that a checkpoint is only resumed by the same run.
+    key: String,
+    interval: Duration,
+    last_saved: Instant,
+}
+
+impl Checkpoint {
+    /// The checkpoint for a run of `name` (e.g. `day15`) described by `key`,
+    /// if checkpoints are on.
+    pub fn new(name: &str, key: String) -> Option<Checkpoint> {
+        let settings = SETTINGS.lock().unwrap().clone();
+        settings.map(|(dir, interval)| Checkpoint::in_dir(dir, interval, name, key))
+    }
+
+    pub fn in_dir(dir: PathBuf, interval: Duration, name: &str, key: String) -> Checkpoint {
+        let path = dir.join(format!("{}-{:016x}.checkpoint", name, fnv1a(&key)));
+        Checkpoint {
+            path,
+            key,
+            interval,
+            last_saved: Instant::now(),
+        }
+    }
+
+    /// Loads the saved state, if there is one for this run and `is_valid`
+    /// accepts it. Anything else is ignored, so that a corrupt checkpoint
+    /// means starting over rather than failing partway through.
+    pub fn load<T: Snapshot>(&self, is_valid: impl FnOnce(&T) -> bool) -> Option<T> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                log!("couldn't read {}: {}", self.path.display(), error);
+                return None;
+            }
+        };
+
+        let header = format!("{}\n{}\n", MAGIC, self.key);
+        let state = bytes
+            .strip_prefix(header.as_bytes())
+            .filter(|words| words.len() % 8 == 0)
+            .and_then(|words| {
+                let words: Vec<u64> = words
+                    .chunks(8)
+                    .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+                    .collect();
+                T::from_words(&words)
+            })
+            .filter(is_valid);
+        match &state {
+            Some(_) => log!("resuming from {}", self.path.display()),
+            None => log!(
+                "ignoring {}, which isn't a valid checkpoint",
+                self.path.display()
+            ),
+        }
+
+        state
+    }
+
+    /// Whether it's time to save again, at the given step. This is cheap
+    /// enough to call every step.
+    pub fn due(&self, step: usize) -> bool {
+        step.is_multiple_of(CHECK_EVERY) && self.last_saved.elapsed() >= self.interval
+    }
+
+    /// Saves the state, replacing the last one. Failing to save is only
+    /// logged, since the run can carry on without it.
+    pub fn save<T: Snapshot>(&mut self, state: &T) {
+        if let Err(error) = self.write(&state.to_words()) {
+            log!("couldn't save {}: {}", self.path.display(), error);
+        }
+        self.last_saved = Instant::now();
+    }
+
+    fn write(&self, words: &[u64]) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // write the whole thing somewhere else first, so that being stopped
+        // halfway through doesn't leave half a checkpoint
+        let partial = self.path.with_extension("partial");
+        let mut out = io::BufWriter::new(fs::File::create(&partial)?);
+        write!(out, "{}\n{}\n", MAGIC, self.key)?;
+        for word in words {
+            out.write_all(&word.to_le_bytes())?;
+        }
+        out.into_inner().map_err(|error| error.into_error())?;
+        fs::rename(&partial, &self.path)?;
+
+        log!("saved {}", self.path.display());
+        Ok(())
+    }
+
+    /// Removes the checkpoint, once the run is done.
+    pub fn finish(self) {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                log!("couldn't remove {}: {}", self.path.display(), error);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u64);
+
+    impl Snapshot for Counter {
+        fn to_words(&self) -> Vec<u64> {
+            vec![self.0]
+        }
+
+        fn from_words(words: &[u64]) -> Option<Counter> {
+            match words {
+                [n] => Some(Counter(*n)),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn save_and_resume() {
+        let dir = std::env::temp_dir().join(format!("aoc-checkpoints-{}", std::process::id()));
+        let checkpoint =
+            |key: &str| Checkpoint::in_dir(dir.clone(), Duration::ZERO, "test", key.to_string());
+
+        let any = |_: &Counter| true;
+        let mut first = checkpoint("a");
+        assert_eq!(first.load(any), None);
+        first.save(&Counter(42));
+        assert!(first.due(CHECK_EVERY) && !first.due(CHECK_EVERY + 1));
+
+        // a different run doesn't see it
+        assert_eq!(checkpoint("b").load(any), None);
+
+        // nor does a run it isn't valid for
+        let second = checkpoint("a");
+        assert_eq!(second.load(|counter: &Counter| counter.0 < 10), None);
+        assert_eq!(second.load(any), Some(Counter(42)));
+        second.finish();
+        assert_eq!(checkpoint("a").load(any), None);
+
+        fs::remove_dir(&dir).unwrap();
+    }
+}
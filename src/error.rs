@@ -0,0 +1,117 @@
+use std::error;
+use std::fmt;
+
+use crate::solver::Part;
+
+/// Errors that can come out of solving a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle input is malformed.
+    Parse(ParseError),
+    /// The input was read fine, but there is no answer for it.
+    NoSolution(String),
+    /// The day doesn't have the requested part.
+    NoSuchPart(u32, Part),
+    /// A puzzle parameter was unknown, or its value was no good.
+    InvalidParam(String),
+    /// The solver was stopped before it finished, having been cancelled.
+    Cancelled,
+}
+
+impl Error {
+    /// Records which day's input a parse error came from.
+    pub fn in_day(self, day: u32) -> Error {
+        match self {
+            Error::Parse(error) => Error::Parse(ParseError {
+                day: Some(day),
+                ..error
+            }),
+            error => error,
+        }
+    }
+
+    /// Records which section of the input a parse error came from, unless it
+    /// is already known.
+    pub fn in_section(self, section: &str) -> Error {
+        match self {
+            Error::Parse(error) => Error::Parse(ParseError {
+                section: error.section.or_else(|| Some(section.to_string())),
+                ..error
+            }),
+            error => error,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Error {
+        Error::Parse(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "could not parse input: {}", error),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+            Error::NoSuchPart(day, part) => write!(f, "day {} has no part {}", day, part),
+            Error::InvalidParam(message) => write!(f, "invalid parameter: {}", message),
+            Error::Cancelled => write!(f, "cancelled before finishing"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Where and why an input could not be parsed.
+///
+/// Lines and columns count from 1. An error at the end of the input points
+/// just past the last line, with an empty `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The day whose input it was, once known.
+    pub day: Option<u32>,
+    /// The section of the input it was in, like `your ticket`, if the input
+    /// has sections.
+    pub section: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    /// The whole line containing the error.
+    pub source: String,
+    /// The part of the line that couldn't be parsed.
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    /// Shows the message, then the line with the offending text underlined:
+    ///
+    /// ```text
+    /// line 3, column 1: unknown operation
+    ///   3 | mul +3
+    ///     | ^^^
+    /// ```
+    ///
+    /// The section, if any, follows the column: `line 6, column 3 (your
+    /// ticket): ...`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(section) = &self.section {
+            write!(f, " ({})", section)?;
+        }
+        write!(f, ": {}", self.message)?;
+
+        if !self.source.is_empty() {
+            let number = self.line.to_string();
+            let padding = " ".repeat(number.len());
+            let offset = " ".repeat(self.column - 1);
+            let underline = "^".repeat(self.text.chars().count().max(1));
+            write!(f, "\n  {} | {}", number, self.source)?;
+            write!(f, "\n  {} | {}{}", padding, offset, underline)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for ParseError {}
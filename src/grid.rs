@@ -0,0 +1,384 @@
+//! A rectangular grid of cells, for the puzzles that are played out on a map.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::Error;
+use crate::parse::{self, Line};
+
+/// A position in a grid, as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// A step from one position to another, as `(rows, columns)`.
+pub type Offset = (isize, isize);
+
+/// Offsets to the 4 orthogonal neighbours.
+pub const ORTHOGONAL: [Offset; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to all 8 neighbours, including the diagonal ones.
+pub const ADJACENT: [Offset; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Cells stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of a grid must be the same length"
+        );
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Reads a grid from a character map, one row per line, converting each
+    /// character with `cell`. `expected` describes the allowed characters for
+    /// errors, e.g. "expected `.` or `#`".
+    pub fn parse(
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, Error> {
+        let grid = Grid::parse_lines(parse::lines(input), cell, expected)?;
+        if grid.is_empty() {
+            return Err(parse::end_of_input(input, "the map is empty"));
+        }
+
+        Ok(grid)
+    }
+
+    /// Like `parse`, but from some of the lines of an input.
+    pub fn parse_lines<'a>(
+        lines: impl Iterator<Item = Line<'a>>,
+        cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, Error> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in lines {
+            let text = line.text.trim();
+
+            let mut row = Vec::new();
+            for (idx, c) in text.char_indices() {
+                match cell(c) {
+                    Some(value) => row.push(value),
+                    None => return Err(line.error(&text[idx..idx + c.len_utf8()], expected)),
+                }
+            }
+
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    let message = format!("expected a row of {} cells", first.len());
+                    return Err(line.error(text, message));
+                }
+            }
+            rows.push(row);
+        }
+
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at the position, as if the grid repeated forever in every
+    /// direction.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+        &self[(row, col)]
+    }
+
+    /// The position one step away, if it's inside the grid.
+    pub fn step(&self, (row, col): Pos, (row_offset, col_offset): Offset) -> Option<Pos> {
+        let row = row.checked_add_signed(row_offset)?;
+        let col = col.checked_add_signed(col_offset)?;
+        if row < self.height && col < self.width {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// The positions one step away in each of the directions, skipping the
+    /// ones outside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [Offset],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    /// The orthogonal neighbours of a position.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// All 8 neighbours of a position.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ADJACENT)
+    }
+
+    /// Looks from `pos` in the direction of `offset`, past any cells that
+    /// `see_through` accepts, and returns where the first other cell is.
+    pub fn cast(&self, pos: Pos, offset: Offset, see_through: impl Fn(&T) -> bool) -> Option<Pos> {
+        let mut pos = self.step(pos, offset)?;
+        while see_through(&self[pos]) {
+            pos = self.step(pos, offset)?;
+        }
+
+        Some(pos)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn count(&self, f: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| f(cell)).count()
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        self.map_with_pos(|_, cell| f(cell))
+    }
+
+    /// Like `map`, but the new cell can depend on where it is.
+    pub fn map_with_pos<U>(&self, f: impl Fn(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            text.extend(row.iter().map(&f));
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid of the given size from the cell at each position.
+    fn build(width: usize, height: usize, cell: impl Fn(Pos) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(cell)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// The part of the grid with its top left corner at `pos`.
+    pub fn subgrid(&self, (row, col): Pos, width: usize, height: usize) -> Grid<T> {
+        Grid::build(width, height, |(r, c)| self[(row + r, col + c)].clone())
+    }
+
+    /// Adds a border of `margin` cells on every side.
+    pub fn expand(&self, margin: usize, fill: T) -> Grid<T> {
+        let width = self.width + 2 * margin;
+        let height = self.height + 2 * margin;
+        Grid::build(width, height, |(row, col)| {
+            match (row.checked_sub(margin), col.checked_sub(margin)) {
+                (Some(row), Some(col)) => self.get((row, col)).unwrap_or(&fill).clone(),
+                _ => fill.clone(),
+            }
+        })
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::build(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid::build(self.height, self.width, |(row, col)| {
+            self[(self.height - 1 - col, row)].clone()
+        })
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Grid<T> {
+        Grid::build(self.height, self.width, |(row, col)| {
+            self[(col, self.width - 1 - row)].clone()
+        })
+    }
+
+    /// Flips the grid on the horizontal axis, so the top row is at the bottom.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid::build(self.width, self.height, |(row, col)| {
+            self[(self.height - 1 - row, col)].clone()
+        })
+    }
+
+    /// Flips the grid on the vertical axis, so the left column is on the right.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        Grid::build(self.width, self.height, |(row, col)| {
+            self[(row, self.width - 1 - col)].clone()
+        })
+    }
+
+    /// The 8 ways the grid can be rotated and flipped: the 4 rotations, then
+    /// the 4 rotations of the flipped grid.
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut orientations = Vec::new();
+        let mut grid = self.clone();
+        for _ in 0..4 {
+            let next = grid.rotate_clockwise();
+            orientations.push(grid);
+            grid = next;
+        }
+
+        grid = grid.flip_horizontal();
+        for _ in 0..4 {
+            let next = grid.rotate_clockwise();
+            orientations.push(grid);
+            grid = next;
+        }
+
+        orientations
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(col < self.width, "column {} is outside the grid", col);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(col < self.width, "column {} is outside the grid", col);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text, Some, "").unwrap()
+    }
+
+    #[test]
+    fn access_and_neighbours() {
+        let g = grid("abc\ndef\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get((1, 2)), Some(&'f'));
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(*g.get_wrapping(-1, 4), 'e');
+        assert_eq!(g.column(1).collect::<String>(), "be");
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(g.neighbours8((1, 1)).count(), 5);
+        assert_eq!(g.cast((0, 0), (0, 1), |&c| c == 'b'), Some((0, 2)));
+        assert_eq!(g.cast((0, 0), (0, 1), |_| true), None);
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("ab\ncd\nef\n");
+        assert_eq!(g.rotate_clockwise().render(|&c| c), "eca\nfdb\n");
+        assert_eq!(g.rotate_anticlockwise().render(|&c| c), "bdf\nace\n");
+        assert_eq!(g.flip_horizontal().render(|&c| c), "ef\ncd\nab\n");
+        assert_eq!(g.flip_vertical().render(|&c| c), "ba\ndc\nfe\n");
+        assert_eq!(g.transpose().render(|&c| c), "ace\nbdf\n");
+        assert_eq!(
+            g.expand(1, '.').render(|&c| c),
+            "....\n.ab.\n.cd.\n.ef.\n....\n"
+        );
+        assert_eq!(g.orientations().len(), 8);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = Grid::parse("ab\nabc\n", Some, "").unwrap_err();
+        assert!(error.to_string().contains("line 2, column 1"));
+    }
+}
@@ -0,0 +1,147 @@
+//! How much heap the solvers use at their peak.
+//!
+//! With the `heap-stats` feature, every allocation goes through an allocator
+//! that keeps count of the bytes each thread has allocated and not yet freed,
+//! and the most there have been. That costs a little on every allocation, so
+//! without the feature nothing is counted and `measure` has nothing to say.
+//!
+//! The counts are per thread, so that parts solved at the same time by `all`
+//! don't get each other's allocations.
+
+use std::cell::Cell;
+
+thread_local! {
+    /// Bytes allocated by the thread and not freed (by it) yet.
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    /// The most `CURRENT` has been.
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Whether allocations are being counted.
+pub fn enabled() -> bool {
+    cfg!(feature = "heap-stats")
+}
+
+/// Runs `f`, and returns what it returns along with the most heap it had
+/// allocated at any one time, in bytes, or `None` if allocations aren't
+/// counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    let start = CURRENT.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start));
+
+    let result = f();
+
+    let peak = PEAK.with(|peak| {
+        let inner_peak = peak.get();
+        // carry on counting for any measurement this one is part of
+        peak.set(inner_peak.max(outer_peak));
+        inner_peak
+    });
+
+    let bytes = (peak - start).max(0) as usize;
+    (result, if enabled() { Some(bytes) } else { None })
+}
+
+/// Formats a number of bytes with a sensible unit, e.g. `1.50MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < units.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2}{}", size, units[unit])
+}
+
+/// Notes `bytes` more (or fewer, if negative) allocated by this thread.
+#[cfg(feature = "heap-stats")]
+fn count(bytes: isize) {
+    // the thread's counts may be gone already while it's shutting down
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + bytes;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+}
+
+#[cfg(feature = "heap-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::count;
+
+    /// The system allocator, counting as it goes.
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                count(layout.size() as isize);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                count(layout.size() as isize);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            count(-(layout.size() as isize));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                count(new_size as isize - layout.size() as isize);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(3 << 20), "3.00MiB");
+        assert_eq!(format_bytes(5 << 40), "5120.00GiB");
+    }
+
+    #[test]
+    fn peaks() {
+        let ((), outer) = measure(|| {
+            let ((), inner) = measure(|| drop(vec![0u8; 1 << 20]));
+            let small = vec![0u8; 1 << 10];
+            if enabled() {
+                assert!(inner.unwrap() >= 1 << 20);
+            } else {
+                assert_eq!(inner, None);
+            }
+            drop(small);
+        });
+
+        // the inner peak counts towards the outer one
+        if enabled() {
+            assert!(outer.unwrap() >= 1 << 20);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Locating and reading puzzle inputs.
+
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Where inputs are looked up when no path is given.
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// The path of a day's input in the inputs directory, in a directory for its
+/// year, e.g. `inputs/2020/day03.txt`.
+pub fn default_path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Reads a puzzle input from the given file, or from stdin if the path is `-`.
+pub fn read(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
@@ -0,0 +1,201 @@
+//! Just enough JSON to write out answers and their details.
+
+use std::fmt;
+
+use crate::error::Error;
+use crate::solver::Answer;
+
+/// A JSON value. Objects keep their keys in the order they were added.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Builds an object from its keys and values.
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    /// Writes compact JSON, all on one line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Int(n) => write!(f, "{}", n),
+            Json::Float(x) if x.is_finite() => write!(f, "{}", x),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+macro_rules! json_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(n: $t) -> Json {
+                    Json::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+json_from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<f64> for Json {
+    fn from(x: f64) -> Json {
+        Json::Float(x)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Json {
+        Json::Bool(b)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Json {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Json {
+        Json::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        match value {
+            Some(value) => value.into(),
+            None => Json::Null,
+        }
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Json {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Number(n) => Json::Int(*n),
+            Answer::Text(s) => Json::String(s.clone()),
+        }
+    }
+}
+
+impl From<&Error> for Json {
+    /// The kind of error and its message, along with where it is for parse
+    /// errors.
+    fn from(error: &Error) -> Json {
+        match error {
+            Error::Parse(parse_error) => Json::object(vec![
+                ("kind", "parse".into()),
+                ("message", parse_error.message.as_str().into()),
+                ("line", parse_error.line.into()),
+                ("column", parse_error.column.into()),
+                ("text", parse_error.text.as_str().into()),
+                (
+                    "section",
+                    parse_error
+                        .section
+                        .as_deref()
+                        .map_or(Json::Null, Json::from),
+                ),
+            ]),
+            Error::NoSolution(message) => Json::object(vec![
+                ("kind", "no_solution".into()),
+                ("message", message.as_str().into()),
+            ]),
+            Error::NoSuchPart(_, _) => Json::object(vec![
+                ("kind", "no_such_part".into()),
+                ("message", error.to_string().into()),
+            ]),
+            Error::InvalidParam(message) => Json::object(vec![
+                ("kind", "invalid_param".into()),
+                ("message", message.as_str().into()),
+            ]),
+            Error::Cancelled => Json::object(vec![
+                ("kind", "cancelled".into()),
+                ("message", error.to_string().into()),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_compact_json() {
+        let value = Json::object(vec![
+            ("day", 16.into()),
+            ("answer", Json::Null),
+            ("list", vec![1.5, 2.0].into()),
+            ("name", "a \"quoted\"\nline".into()),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"day":16,"answer":null,"list":[1.5,2],"name":"a \"quoted\"\nline"}"#
+        );
+    }
+}
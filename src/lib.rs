@@ -0,0 +1,43 @@
+//! Advent of Code solutions, starting with 2020.
+//!
+//! Each year's puzzles are in a module of their own, like [`y2020`], where
+//! every day implements [`Solver`] and is listed in the year's `YEAR`. The
+//! years are listed in [`YEARS`]:
+//!
+//! ```
+//! use aoc_2020_rust::y2020::day05::Day5;
+//! use aoc_2020_rust::{Answer, Part, Solver};
+//!
+//! let answer = Day5::solve("FBFBBFFRLR\n", Part::One).unwrap();
+//! assert_eq!(answer, Answer::Number(357));
+//! ```
+
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod cache;
+pub mod cancel;
+pub mod checkpoint;
+pub mod error;
+pub mod grid;
+pub mod hash;
+pub mod heap;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod params;
+pub mod parse;
+pub mod pool;
+pub mod progress;
+pub mod render;
+pub mod repl;
+pub mod rng;
+pub mod scaffold;
+pub mod serve;
+pub mod solver;
+pub mod trace;
+pub mod y2020;
+
+pub use error::{Error, ParseError};
+pub use params::{Param, Params};
+pub use solver::{Answer, Example, Part, Solution, Solver, Year, YEARS};
@@ -0,0 +1,28 @@
+//! A channel for the progress and debugging output of the solvers, kept
+//! apart from the answers.
+//!
+//! Messages go to stderr, so that stdout only has answers on it, and can be
+//! turned off altogether.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns the log on or off (it starts on).
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Like `eprintln!`, but silenced when the log is off.
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -1,82 +1,1364 @@
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal, LineWriter, Write};
+use std::panic;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use aoc_2020_rust::answers::{self, Answers, Verdict};
+use aoc_2020_rust::bench::{self, Baseline};
+use aoc_2020_rust::cache::{self, Cache, Entry, Key};
+use aoc_2020_rust::checkpoint;
+use aoc_2020_rust::heap;
+use aoc_2020_rust::input;
+use aoc_2020_rust::json::Json;
+use aoc_2020_rust::log;
+use aoc_2020_rust::pool;
+use aoc_2020_rust::render::{self, Frame};
+use aoc_2020_rust::repl::Repl;
+use aoc_2020_rust::scaffold;
+use aoc_2020_rust::serve::{self, Server};
+use aoc_2020_rust::solver;
+use aoc_2020_rust::trace;
+use aoc_2020_rust::{Answer, Error, Params, Part, Solution, Year};
+
+const USAGE: &str = "usage: aoc-2020-rust <command> [options]
+
+commands:
+    list                    list the available days and their parameters
+    new                     add a skeleton for a new day (needs --day; see the README)
+    day <n>                 solve a day
+    all                     solve every day in parallel, with a summary table
+    verify [<n>...]         check every day (or the given days) against the answers file
+    check [<n>...]          check every day's input (or the given days') for problems,
+                            without solving
+    bench [<n>...]          time parsing and solving for every day (or the given days)
+    generate <n>            print a random input for a day
+    render <n>              draw each step of a day that plays out on a map, as images and text
+    serve                   answer puzzles over HTTP on localhost (see the README)
+    repl                    try single lines of input at a prompt (`help` lists the commands)
+    cache clear             remove the answers all and verify have kept
+
+options:
+    --year <y>              which year's puzzles (default: the latest)
+    --day <n>               the day new adds
+    --part <p>              only solve part 1 or 2 of the day
+    --input <path>          read the day's input from a file (`-` for stdin); for repl, the
+                            file day 19's rules are read from
+    --inputs <dir>          directory of inputs named <year>/dayNN.txt (default: inputs)
+    --answers <path>        answers file for verify (default: answers.toml)
+    --cache <dir>           directory all and verify keep answers in, so that parts are only
+                            solved again when the input, parameters or solver change
+                            (default: cache)
+    --no-cache              solve every part, without using or keeping answers in the cache
+    --record                save answers for days that don't have one yet
+    --param <key>=<value>   change one of the day's parameters (see list; day, bench, render
+                            and generate)
+    --example               solve the examples from the puzzle statements instead of the inputs
+                            (day, all and render)
+    --format <f>            `text` (default), or `json` for one object per line with the answer,
+                            runtime and details of each part (day and all only)
+    --quiet                 don't show the solvers' progress and debugging output
+    --trace                 write a step-by-step trace of what the solvers do to stderr
+                            (day and all only)
+    --trace-kinds <k>,...   only trace these kinds of event, e.g. `round,game` (implies --trace)
+    --trace-file <path>     write the trace to a file instead (implies --trace)
+    --checkpoint <dir>      save the state of day 15 and 23's long loops in a directory every
+                            so often, and carry on from a saved state (day only)
+    --checkpoint-every <s>  how many seconds apart checkpoints are (default: 30)
+    --jobs <n>              number of threads for all, and for serve to solve on
+                            (default: one per core)
+    --runs <n>              number of times bench runs each part (default: 5)
+    --baseline <path>       baseline file for bench (default: bench.toml)
+    --save-baseline         save the median times from bench as the baseline
+    --compare               compare bench against the baseline, failing on slowdowns
+    --threshold <percent>   how much slower than the baseline counts as a slowdown (default: 10)
+    --size <n>              how big a generated input is; what it counts depends on the day
+                            (default: about the size of a real input)
+    --seed <n>              seed for generate, which always gives the same input for a seed
+                            (default: 0)
+    --out <dir>             directory render writes its frames to (default: frames)
+    --scale <n>             pixels per cell in rendered images (default: 4)
+    --pbm                   render black and white PBM images instead of PPM
+    --port <n>              port for serve to listen on (default: 2020)
+    --timeout <s>           how many seconds serve gives each request to be solved (default: 60)";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!();
+    eprintln!("{}", USAGE);
+    exit(2);
+}
+
+/// How answers are printed.
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Command line options shared by the commands.
+struct Options {
+    positional: Vec<String>,
+    year: Option<u32>,
+    new_day: Option<u32>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    inputs_dir: PathBuf,
+    answers: PathBuf,
+    cache_dir: PathBuf,
+    no_cache: bool,
+    record: bool,
+    params: Params,
+    example: bool,
+    format: Format,
+    quiet: bool,
+    trace: bool,
+    trace_kinds: Option<Vec<String>>,
+    trace_file: Option<PathBuf>,
+    checkpoint: Option<PathBuf>,
+    checkpoint_every: Duration,
+    jobs: usize,
+    runs: usize,
+    baseline: PathBuf,
+    save_baseline: bool,
+    compare: bool,
+    threshold: f64,
+    size: Option<usize>,
+    seed: u64,
+    out: PathBuf,
+    scale: usize,
+    pbm: bool,
+    port: u16,
+    timeout: Duration,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Options {
+        let mut options = Options {
+            positional: Vec::new(),
+            year: None,
+            new_day: None,
+            part: None,
+            input: None,
+            inputs_dir: PathBuf::from(input::DEFAULT_DIR),
+            answers: PathBuf::from(answers::DEFAULT_PATH),
+            cache_dir: PathBuf::from(cache::DEFAULT_DIR),
+            no_cache: false,
+            record: false,
+            params: Params::new(),
+            example: false,
+            format: Format::Text,
+            quiet: false,
+            trace: false,
+            trace_kinds: None,
+            trace_file: None,
+            checkpoint: None,
+            checkpoint_every: checkpoint::DEFAULT_INTERVAL,
+            jobs: pool::default_threads(),
+            runs: 5,
+            baseline: PathBuf::from(bench::DEFAULT_BASELINE),
+            save_baseline: false,
+            compare: false,
+            threshold: 10.0,
+            size: None,
+            seed: 0,
+            out: PathBuf::from("frames"),
+            scale: 4,
+            pbm: false,
+            port: 2020,
+            timeout: serve::DEFAULT_TIMEOUT,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || match args.next() {
+                Some(value) => value.clone(),
+                None => usage_error(&format!("missing value for {}", arg)),
+            };
+
+            match arg.as_str() {
+                "--year" => match value().parse::<u32>() {
+                    Ok(year) => options.year = Some(year),
+                    Err(_) => usage_error("--year must be a number"),
+                },
+                "--day" => match value().parse::<u32>() {
+                    Ok(day) if (1..=25).contains(&day) => options.new_day = Some(day),
+                    _ => usage_error("--day must be from 1 to 25"),
+                },
+                "--part" => {
+                    let part = value().parse::<u32>().ok().and_then(Part::from_number);
+                    match part {
+                        Some(part) => options.part = Some(part),
+                        None => usage_error("--part must be 1 or 2"),
+                    }
+                }
+                "--input" => options.input = Some(PathBuf::from(value())),
+                "--inputs" => options.inputs_dir = PathBuf::from(value()),
+                "--answers" => options.answers = PathBuf::from(value()),
+                "--cache" => options.cache_dir = PathBuf::from(value()),
+                "--no-cache" => options.no_cache = true,
+                "--record" => options.record = true,
+                "--param" => {
+                    if let Err(message) = options.params.set_assignment(&value()) {
+                        usage_error(&format!("--param: {}", message));
+                    }
+                }
+                "--example" => options.example = true,
+                "--format" => match value().as_str() {
+                    "text" => options.format = Format::Text,
+                    "json" => options.format = Format::Json,
+                    _ => usage_error("--format must be text or json"),
+                },
+                "--quiet" => options.quiet = true,
+                "--trace" => options.trace = true,
+                "--trace-kinds" => {
+                    let kinds: Vec<String> = value()
+                        .split(',')
+                        .map(|kind| kind.trim().to_string())
+                        .filter(|kind| !kind.is_empty())
+                        .collect();
+                    if kinds.is_empty() {
+                        usage_error("--trace-kinds needs at least one kind");
+                    }
+                    options.trace_kinds = Some(kinds);
+                    options.trace = true;
+                }
+                "--trace-file" => {
+                    options.trace_file = Some(PathBuf::from(value()));
+                    options.trace = true;
+                }
+                "--checkpoint" => options.checkpoint = Some(PathBuf::from(value())),
+                "--checkpoint-every" => match value().parse::<f64>() {
+                    Ok(secs) if secs >= 0.0 => {
+                        options.checkpoint_every = Duration::from_secs_f64(secs)
+                    }
+                    _ => usage_error("--checkpoint-every must be a non-negative number"),
+                },
+                "--jobs" => match value().parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => options.jobs = jobs,
+                    _ => usage_error("--jobs must be a positive number"),
+                },
+                "--runs" => match value().parse::<usize>() {
+                    Ok(runs) if runs > 0 => options.runs = runs,
+                    _ => usage_error("--runs must be a positive number"),
+                },
+                "--baseline" => options.baseline = PathBuf::from(value()),
+                "--save-baseline" => options.save_baseline = true,
+                "--compare" => options.compare = true,
+                "--threshold" => match value().parse::<f64>() {
+                    Ok(threshold) if threshold >= 0.0 => options.threshold = threshold,
+                    _ => usage_error("--threshold must be a non-negative number"),
+                },
+                "--size" => match value().parse::<usize>() {
+                    Ok(size) if size > 0 => options.size = Some(size),
+                    _ => usage_error("--size must be a positive number"),
+                },
+                "--seed" => match value().parse::<u64>() {
+                    Ok(seed) => options.seed = seed,
+                    Err(_) => usage_error("--seed must be a number"),
+                },
+                "--out" => options.out = PathBuf::from(value()),
+                "--scale" => match value().parse::<usize>() {
+                    Ok(scale) if scale > 0 => options.scale = scale,
+                    _ => usage_error("--scale must be a positive number"),
+                },
+                "--pbm" => options.pbm = true,
+                "--port" => match value().parse::<u16>() {
+                    Ok(port) => options.port = port,
+                    Err(_) => usage_error("--port must be a port number"),
+                },
+                "--timeout" => match value().parse::<f64>() {
+                    Ok(secs) if secs > 0.0 => options.timeout = Duration::from_secs_f64(secs),
+                    _ => usage_error("--timeout must be a positive number"),
+                },
+                _ if arg.starts_with("--") => usage_error(&format!("unknown option `{}`", arg)),
+                _ => options.positional.push(arg.clone()),
+            }
+        }
+
+        if options.example && options.input.is_some() {
+            usage_error("--example can't be used with --input");
+        }
+
+        options
+    }
+
+    /// The year given with `--year`, or the latest one.
+    fn year(&self) -> &'static Year {
+        match self.year {
+            None => solver::latest(),
+            Some(year) => match solver::year(year) {
+                Some(year) => year,
+                None => usage_error(&format!("no solutions for {}", year)),
+            },
+        }
+    }
+
+    /// Looks up the solution for a day given on the command line.
+    fn find_solution(&self, arg: &str) -> &'static dyn Solution {
+        let day = match arg.parse::<u32>() {
+            Ok(n) => n,
+            Err(_) => usage_error(&format!("invalid day `{}`", arg)),
+        };
+        let year = self.year();
+        match year.find(day) {
+            Some(solution) => solution,
+            None => usage_error(&format!("no solution for day {} of {}", day, year.year)),
+        }
+    }
+
+    /// The days given on the command line, or every day if there are none.
+    fn solutions(&self) -> Vec<&'static dyn Solution> {
+        if self.positional.is_empty() {
+            self.year().days.to_vec()
+        } else {
+            self.positional
+                .iter()
+                .map(|arg| self.find_solution(arg))
+                .collect()
+        }
+    }
+
+    /// The cache of answers, unless it's turned off. The examples are quick
+    /// enough not to need it, and the details for the JSON output aren't
+    /// kept, so it's not used for those either.
+    fn cache(&self) -> Option<Cache> {
+        match self.no_cache || self.example || self.format == Format::Json {
+            true => None,
+            false => Some(Cache::new(self.cache_dir.clone())),
+        }
+    }
+
+    /// Checks that the day has the parameters given with `--param`.
+    fn check_params(&self, solution: &dyn Solution) {
+        if let Err(error) = self.params.check(solution.day(), solution.params()) {
+            usage_error(&error.to_string());
+        }
+    }
+
+    /// The parts of the day to solve.
+    fn parts(&self, solution: &dyn Solution) -> Vec<Part> {
+        match self.part {
+            None => solution.parts().to_vec(),
+            Some(part) if solution.parts().contains(&part) => vec![part],
+            Some(part) => usage_error(&format!("day {} has no part {}", solution.day(), part)),
+        }
+    }
+
+    /// Reads the input for the day, from `--input` if given.
+    fn read_input(&self, day: u32) -> Result<String, String> {
+        let path = match &self.input {
+            Some(path) => path.clone(),
+            None => input::default_path(&self.inputs_dir, self.year().year, day),
+        };
+
+        input::read(&path).map_err(|error| format!("could not read {}: {}", path.display(), error))
+    }
+}
+
+fn list(options: &Options) {
+    for solution in options.year().days {
+        let parts: Vec<_> = solution.parts().iter().map(|p| p.to_string()).collect();
+        println!(
+            "day {:2}: {} (parts {})",
+            solution.day(),
+            solution.title(),
+            parts.join(", ")
+        );
+
+        for param in solution.params() {
+            let mut default = format!("{}={}", param.name, param.default);
+            if let Some(part) = param.part {
+                default.push_str(&format!(" (part {})", part));
+            }
+            println!("        {:<24}  {}", default, param.help);
+        }
+    }
+}
+
+/// What came of solving one part, and how long it took.
+struct Run {
+    result: Result<(Answer, Option<Json>), Error>,
+    elapsed: Duration,
+    /// The most heap solving used at once, with the `heap-stats` feature.
+    heap: Option<usize>,
+    /// Whether the answer came from the cache, in which case `elapsed` is
+    /// how long it took when it was solved.
+    cached: bool,
+}
+
+impl Run {
+    /// Whether there's an answer, and it's the expected one if there is one.
+    fn ok(&self, example: Option<(usize, &str)>) -> bool {
+        match (&self.result, example) {
+            (Ok((answer, _)), Some((_, expected))) => answer.to_string() == expected,
+            (Ok(_), None) => true,
+            (Err(_), _) => false,
+        }
+    }
+}
+
+/// Solves one part of a day with the given parameter overrides, only
+/// collecting the details for the JSON output. With a cache, a part solved
+/// before is looked up instead, and a new answer is kept.
+fn run_part(
+    options: &Options,
+    cache: Option<&Cache>,
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+) -> Run {
+    let year = options.year().year;
+    let key = cache.and_then(|_| Key::new(year, solution, part, params, input).ok());
+    if let (Some(cache), Some(key)) = (cache, &key) {
+        if let Some(entry) = cache.get(key) {
+            return Run {
+                result: Ok((entry.answer, None)),
+                elapsed: entry.elapsed,
+                heap: None,
+                cached: true,
+            };
+        }
+    }
+
+    let start = Instant::now();
+    let (result, heap) = heap::measure(|| match options.format {
+        Format::Text => solution
+            .run(input, part, params)
+            .map(|answer| (answer, None)),
+        Format::Json => solution.run_with_details(input, part, params),
+    });
+    let elapsed = start.elapsed();
+
+    if let (Some(cache), Some(key), Ok((answer, _))) = (cache, &key, &result) {
+        let entry = Entry {
+            answer: answer.clone(),
+            elapsed,
+        };
+        if let Err(error) = cache.put(key, &entry) {
+            eprintln!("couldn't save to {}: {}", cache.dir().display(), error);
+        }
+    }
+
+    Run {
+        result,
+        elapsed,
+        heap,
+        cached: false,
+    }
+}
+
+/// Solves one part of a day and prints the answer, returning false if it
+/// failed. `example` is the number of the example and its expected answer,
+/// when solving one of those.
+fn solve_part(
+    options: &Options,
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+    example: Option<(usize, &str)>,
+) -> bool {
+    let run = run_part(options, None, solution, part, input, params);
+    print_run(options.format, solution, part, example, &run)
+}
+
+/// Prints the answer to one part, returning false if it failed.
+fn print_run(
+    format: Format,
+    solution: &dyn Solution,
+    part: Part,
+    example: Option<(usize, &str)>,
+    run: &Run,
+) -> bool {
+    let ok = run.ok(example);
+
+    match format {
+        Format::Text => {
+            let mut label = format!("day {} part {}", solution.day(), part);
+            if let Some((n, _)) = example {
+                label.push_str(&format!(" example {}", n));
+            }
+
+            match (&run.result, example) {
+                (Ok((answer, _)), Some((_, expected))) if !ok => {
+                    println!("{}: {} (expected {})", label, answer, expected)
+                }
+                (Ok((answer, _)), _) => match run.heap {
+                    Some(heap) => println!(
+                        "{}: {} (peak heap {})",
+                        label,
+                        answer,
+                        heap::format_bytes(heap)
+                    ),
+                    None => println!("{}: {}", label, answer),
+                },
+                (Err(error), _) => eprintln!("{}: {}", label, error),
+            }
+        }
+        Format::Json => {
+            let mut fields = json_fields(solution, part, example);
+            match &run.result {
+                Ok((answer, details)) => {
+                    fields.push(("answer", answer.into()));
+                    if let Some(details) = details {
+                        fields.push(("details", details.clone()));
+                    }
+                }
+                Err(error) => fields.push(("error", error.into())),
+            }
+            fields.push(("time_ms", (run.elapsed.as_micros() as f64 / 1000.0).into()));
+            if let Some(heap) = run.heap {
+                fields.push(("heap_bytes", heap.into()));
+            }
+            println!("{}", Json::object(fields));
+        }
+    }
+
+    ok
+}
+
+/// The fields saying which part (and example) a JSON line is about.
+fn json_fields(
+    solution: &dyn Solution,
+    part: Part,
+    example: Option<(usize, &str)>,
+) -> Vec<(&'static str, Json)> {
+    let mut fields = vec![
+        ("day", solution.day().into()),
+        ("part", part.number().into()),
+    ];
+    if let Some((n, expected)) = example {
+        fields.push(("example", n.into()));
+        fields.push(("expected", expected.into()));
+    }
+
+    fields
+}
+
+/// Reports an input that couldn't be read, for each part of the day.
+fn input_error(format: Format, solution: &dyn Solution, parts: &[Part], message: &str) {
+    match format {
+        Format::Text => eprintln!("day {}: {}", solution.day(), message),
+        Format::Json => {
+            for part in parts {
+                let error =
+                    Json::object(vec![("kind", "input".into()), ("message", message.into())]);
+                let mut fields = json_fields(solution, *part, None);
+                fields.push(("error", error));
+                println!("{}", Json::object(fields));
+            }
+        }
+    }
+}
+
+/// Solves the given parts of a day, returning false if any of them failed.
+fn run_day(options: &Options, solution: &dyn Solution, parts: &[Part], input: &str) -> bool {
+    let mut ok = true;
+    for part in parts {
+        ok &= solve_part(options, solution, *part, input, &options.params, None);
+    }
+
+    ok
+}
+
+/// Solves the examples for the given parts of a day, returning false if any
+/// of them gave the wrong answer.
+fn run_examples(options: &Options, solution: &dyn Solution, parts: &[Part]) -> bool {
+    let mut ok = true;
+    for (idx, example) in solution.examples().iter().enumerate() {
+        for part in parts {
+            if let Some(expected) = example.expected(*part) {
+                let expected = Some((idx + 1, expected));
+                let params = example.params(&options.params);
+                ok &= solve_part(options, solution, *part, example.input, &params, expected);
+            }
+        }
+    }
+
+    ok
+}
+
+fn day(options: &Options) {
+    let solution = match options.positional.first() {
+        Some(arg) => options.find_solution(arg),
+        None => usage_error("missing day"),
+    };
+    let day = solution.day();
+    options.check_params(solution);
+
+    let parts = options.parts(solution);
+    if options.example {
+        if solution.examples().is_empty() {
+            eprintln!("day {} has no examples", day);
+            exit(1);
+        }
+        if !run_examples(options, solution, &parts) {
+            exit(1);
+        }
+        return;
+    }
+
+    let input = match options.read_input(day) {
+        Ok(input) => input,
+        Err(message) => {
+            input_error(options.format, solution, &parts, &message);
+            exit(1);
+        }
+    };
+
+    if !run_day(options, solution, &parts, &input) {
+        exit(1);
+    }
+}
+
+fn all(options: &Options) {
+    if options.input.is_some() {
+        usage_error("--input can only be used with a single day; use --inputs instead");
+    }
+
+    /// One part of a day (or of one of its examples) to solve.
+    struct Job<'a> {
+        solution: &'static dyn Solution,
+        part: Part,
+        /// The input, or why it couldn't be read.
+        input: Result<&'a str, &'a str>,
+        params: Params,
+        example: Option<(usize, &'static str)>,
+    }
+
+    let days = options.year().days;
+    let inputs: Vec<Result<String, String>> = days
+        .iter()
+        .map(|solution| match options.example {
+            true => Ok(String::new()),
+            false => options.read_input(solution.day()),
+        })
+        .collect();
+
+    let mut jobs = Vec::new();
+    for (solution, input) in days.iter().zip(&inputs) {
+        let parts = options.parts(*solution);
+        // the JSON lines are printed in order along with the answers
+        if let (Err(message), Format::Text) = (input, options.format) {
+            input_error(options.format, *solution, &parts, message);
+        }
+
+        if options.example {
+            for (idx, example) in solution.examples().iter().enumerate() {
+                for part in &parts {
+                    if let Some(expected) = example.expected(*part) {
+                        jobs.push(Job {
+                            solution: *solution,
+                            part: *part,
+                            input: Ok(example.input),
+                            params: example.params(&options.params),
+                            example: Some((idx + 1, expected)),
+                        });
+                    }
+                }
+            }
+        } else {
+            for part in parts {
+                jobs.push(Job {
+                    solution: *solution,
+                    part,
+                    input: input.as_deref().map_err(String::as_str),
+                    params: options.params.clone(),
+                    example: None,
+                });
+            }
+        }
+    }
+
+    // panics are reported in the table, so the default hook would only repeat them
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let cache = options.cache();
+    let start = Instant::now();
+    let runs = pool::map(&jobs, options.jobs, |job| {
+        job.input.ok().map(|input| {
+            let cache = cache.as_ref();
+            run_part(options, cache, job.solution, job.part, input, &job.params)
+        })
+    });
+    let wall_time = start.elapsed();
+    panic::set_hook(hook);
+
+    let mut n_failed = 0;
+    let mut n_cached = 0;
+    let mut total_time = Duration::default();
+
+    if options.format == Format::Text {
+        let example = if options.example { "  example" } else { "" };
+        let peak = if heap::enabled() { "  peak heap" } else { "" };
+        println!(
+            "{:>3}  {:>4}{}  {:<8}  {:>9}{}  answer",
+            "day", "part", example, "status", "time", peak
+        );
+    }
+    for (job, run) in jobs.iter().zip(&runs) {
+        match run {
+            Ok(Some(run)) if run.cached => n_cached += 1,
+            Ok(Some(run)) => total_time += run.elapsed,
+            _ => (),
+        }
+
+        if options.format == Format::Json {
+            match run {
+                Ok(Some(run)) => {
+                    if !print_run(options.format, job.solution, job.part, job.example, run) {
+                        n_failed += 1;
+                    }
+                }
+                Ok(None) => {
+                    let message = job.input.err().unwrap_or_default();
+                    input_error(options.format, job.solution, &[job.part], message);
+                }
+                Err(message) => {
+                    n_failed += 1;
+                    let error = Json::object(vec![
+                        ("kind", "panic".into()),
+                        ("message", message.as_str().into()),
+                    ]);
+                    let mut fields = json_fields(job.solution, job.part, job.example);
+                    fields.push(("error", error));
+                    println!("{}", Json::object(fields));
+                }
+            }
+            continue;
+        }
+
+        let (status, time, answer) = match run {
+            Ok(None) => ("no input", String::new(), String::new()),
+            Err(message) => ("panic", String::new(), message.clone()),
+            Ok(Some(run)) => {
+                let time = bench::format_duration(run.elapsed);
+                match (&run.result, job.example) {
+                    (Err(error), _) => {
+                        // only the first line of parse errors fits in the table
+                        let message = error.to_string();
+                        let message = message.lines().next().unwrap_or_default();
+                        ("error", time, message.to_string())
+                    }
+                    (Ok((answer, _)), Some((_, expected))) if !run.ok(job.example) => {
+                        ("wrong", time, format!("{} (expected {})", answer, expected))
+                    }
+                    (Ok((answer, _)), _) if run.cached => ("cached", time, answer.to_string()),
+                    (Ok((answer, _)), _) => ("ok", time, answer.to_string()),
+                }
+            }
+        };
+        if matches!(status, "error" | "panic" | "wrong") {
+            n_failed += 1;
+        }
+
+        let example = match job.example {
+            Some((n, _)) => format!("  {:>7}", n),
+            None => String::new(),
+        };
+        let peak = match run {
+            _ if !heap::enabled() => String::new(),
+            Ok(Some(Run {
+                heap: Some(peak), ..
+            })) => {
+                format!("  {:>9}", heap::format_bytes(*peak))
+            }
+            _ => format!("  {:>9}", ""),
+        };
+        let row = format!(
+            "{:>3}  {:>4}{}  {:<8}  {:>9}{}  {}",
+            job.solution.day(),
+            job.part,
+            example,
+            status,
+            time,
+            peak,
+            answer
+        );
+        println!("{}", row.trim_end());
+    }
+
+    if options.format == Format::Text {
+        println!(
+            "{} parts in {} on {} threads ({} of solving)",
+            jobs.len(),
+            bench::format_duration(wall_time),
+            options.jobs,
+            bench::format_duration(total_time)
+        );
+        if n_cached > 0 {
+            println!(
+                "{} answers from {} (--no-cache to solve them again)",
+                n_cached,
+                options.cache_dir.display()
+            );
+        }
+        if n_failed > 0 {
+            println!("{} failed", n_failed);
+        }
+    }
+
+    if n_failed > 0 {
+        exit(1);
+    }
+}
+
+fn verify(options: &Options) {
+    let mut answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("could not read {}: {}", options.answers.display(), error);
+            exit(1);
+        }
+    };
+
+    let year = options.year().year;
+    let solutions = options.solutions();
+    let cache = options.cache();
+
+    let mut n_failed = 0;
+    let mut n_recorded = 0;
+    let mut n_cached = 0;
+
+    println!(
+        "{:>3}  {:>4}  {:<9}  {:<20}  expected",
+        "day", "part", "status", "answer"
+    );
+    for solution in solutions {
+        let input = match options.read_input(solution.day()) {
+            Ok(input) => Some(input),
+            Err(message) => {
+                eprintln!("day {}: {}", solution.day(), message);
+                None
+            }
+        };
+
+        for part in options.parts(solution) {
+            let expected = answers
+                .get(year, solution.day(), part)
+                .map(|s| s.to_string());
+
+            let run = input.as_ref().map(|input| {
+                run_part(
+                    options,
+                    cache.as_ref(),
+                    solution,
+                    part,
+                    input,
+                    &options.params,
+                )
+            });
+            if run.as_ref().is_some_and(|run| run.cached) {
+                n_cached += 1;
+            }
+
+            let (status, answer) = match run {
+                None => ("no input", String::new()),
+                Some(run) => match run.result {
+                    Err(error) => {
+                        // only the first line of parse errors fits in the table
+                        n_failed += 1;
+                        let message = error.to_string();
+                        (
+                            "error",
+                            message.lines().next().unwrap_or_default().to_string(),
+                        )
+                    }
+                    Ok((answer, _)) => {
+                        let answer = answer.to_string();
+                        let verdict =
+                            answers.verify(year, solution.day(), part, &answer, options.record);
+                        match verdict {
+                            Verdict::Fail => n_failed += 1,
+                            Verdict::Recorded => n_recorded += 1,
+                            Verdict::Pass | Verdict::Unknown => (),
+                        }
+                        (verdict.as_str(), answer)
+                    }
+                },
+            };
+
+            let row = format!(
+                "{:>3}  {:>4}  {:<9}  {:<20}  {}",
+                solution.day(),
+                part,
+                status,
+                answer,
+                expected.unwrap_or_default()
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+
+    if n_cached > 0 {
+        println!(
+            "{} answers from {} (--no-cache to solve them again)",
+            n_cached,
+            options.cache_dir.display()
+        );
+    }
+    if n_recorded > 0 {
+        if let Err(error) = answers.save(&options.answers) {
+            eprintln!("could not write {}: {}", options.answers.display(), error);
+            exit(1);
+        }
+        println!(
+            "recorded {} answers in {}",
+            n_recorded,
+            options.answers.display()
+        );
+    }
+
+    if n_failed > 0 {
+        println!("{} failed", n_failed);
+        exit(1);
+    }
+}
+
+fn check(options: &Options) {
+    if options.example {
+        usage_error("--example can't be used with check");
+    }
+
+    let mut n_problems = 0;
+    for solution in options.solutions() {
+        let day = solution.day();
+        let input = match options.read_input(day) {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("day {}: {}", day, message);
+                continue;
+            }
+        };
+
+        let mut problems = solution.check(&input);
+        let warnings = solution.warnings(&input);
+        problems.sort_by_key(|problem| match problem {
+            Error::Parse(error) => error.line,
+            _ => 0,
+        });
+
+        let count =
+            |n: usize, what: &str| format!("{} {}{}", n, what, if n == 1 { "" } else { "s" });
+        match (problems.len(), warnings.len()) {
+            (0, 0) => println!("day {}: ok", day),
+            (0, n) => println!("day {}: ok, with {}", day, count(n, "warning")),
+            (n, _) => println!("day {}: {}", day, count(n, "problem")),
+        }
+        for problem in &problems {
+            println!("{}", problem);
+        }
+        for warning in &warnings {
+            println!("warning: {}", warning);
+        }
+        n_problems += problems.len();
+    }
+
+    if n_problems > 0 {
+        exit(1);
+    }
+}
+
+fn bench(options: &Options) {
+    if options.example {
+        usage_error("--example can't be used with bench");
+    }
+
+    let baseline = if options.compare {
+        match Baseline::load(&options.baseline) {
+            Ok(baseline) => Some(baseline),
+            Err(error) => {
+                eprintln!("could not read {}: {}", options.baseline.display(), error);
+                exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let stats = |stats: bench::Stats| {
+        format!(
+            "{} / {} / {}",
+            bench::format_duration(stats.min),
+            bench::format_duration(stats.median),
+            bench::format_duration(stats.max)
+        )
+    };
+
+    let year = options.year().year;
+    let solutions = options.solutions();
+    for solution in &solutions {
+        options.check_params(*solution);
+    }
+
+    let mut measured = Baseline::default();
+    let mut n_failed = 0;
+    let mut n_slower = 0;
+
+    let peak_header = if heap::enabled() { "  peak heap" } else { "" };
+    println!(
+        "{:>3}  {:>4}  {:<28}  {:<28}{}  vs baseline",
+        "day", "part", "parse (min / median / max)", "solve (min / median / max)", peak_header
+    );
+    for solution in solutions {
+        let input = match options.read_input(solution.day()) {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("day {}: {}", solution.day(), message);
+                n_failed += 1;
+                continue;
+            }
+        };
+
+        for part in options.parts(solution) {
+            let measurement =
+                match bench::measure(solution, &input, part, &options.params, options.runs) {
+                    Ok(measurement) => measurement,
+                    Err(error) => {
+                        let message = error.to_string();
+                        let message = message.lines().next().unwrap_or_default();
+                        eprintln!("day {} part {}: {}", solution.day(), part, message);
+                        n_failed += 1;
+                        continue;
+                    }
+                };
+            measured.insert(year, solution.day(), part, &measurement);
+
+            let old = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.get(year, solution.day(), part));
+            let comparison = match old {
+                None => String::new(),
+                Some(old) => {
+                    let old_time = old.parse + old.solve;
+                    let new_time = measurement.total();
+                    let mut comparison = format!("{:+.1}%", bench::change(old_time, new_time));
+                    if bench::is_slowdown(old_time, new_time, options.threshold) {
+                        n_slower += 1;
+                        comparison.push_str(" SLOWER");
+                    }
+                    if let (Some(old), Some(new)) = (old.heap, measurement.heap) {
+                        let change = match old {
+                            0 => 0.0,
+                            _ => (new as f64 - old as f64) / old as f64 * 100.0,
+                        };
+                        comparison.push_str(&format!(", heap {:+.1}%", change));
+                    }
+                    comparison
+                }
+            };
+
+            let peak = match measurement.heap {
+                Some(peak) => format!("  {:>9}", heap::format_bytes(peak)),
+                None => String::new(),
+            };
+            let row = format!(
+                "{:>3}  {:>4}  {:<28}  {:<28}{}  {}",
+                solution.day(),
+                part,
+                stats(measurement.parse),
+                stats(measurement.solve),
+                peak,
+                comparison
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+
+    if options.save_baseline {
+        if let Err(error) = measured.save(&options.baseline) {
+            eprintln!("could not write {}: {}", options.baseline.display(), error);
+            exit(1);
+        }
+        println!("saved baseline in {}", options.baseline.display());
+    }
+
+    if n_slower > 0 {
+        println!(
+            "{} slower than the baseline by more than {}%",
+            n_slower, options.threshold
+        );
+    }
+    if n_failed > 0 || n_slower > 0 {
+        exit(1);
+    }
+}
+
+fn generate(options: &Options) {
+    let solution = match options.positional.first() {
+        Some(arg) => options.find_solution(arg),
+        None => usage_error("missing day"),
+    };
+
+    let size = options.size.unwrap_or_else(|| solution.input_size());
+    match solution.generate(size, options.seed, &options.params) {
+        Ok(Some(input)) => print!("{}", input),
+        Ok(None) => {
+            eprintln!("day {} has no input generator", solution.day());
+            exit(1);
+        }
+        Err(error) => {
+            eprintln!("day {}: {}", solution.day(), error);
+            exit(1);
+        }
+    }
+}
+
+fn render(options: &Options) {
+    let solution = match options.positional.first() {
+        Some(arg) => options.find_solution(arg),
+        None => usage_error("missing day"),
+    };
+    let day = solution.day();
+    options.check_params(solution);
+
+    let (input, params) = if options.example {
+        match solution.examples().first() {
+            Some(example) => (example.input.to_string(), example.params(&options.params)),
+            None => {
+                eprintln!("day {} has no examples", day);
+                exit(1);
+            }
+        }
+    } else {
+        match options.read_input(day) {
+            Ok(input) => (input, options.params.clone()),
+            Err(message) => {
+                eprintln!("{}", message);
+                exit(1);
+            }
+        }
+    };
+
+    if let Err(error) = fs::create_dir_all(&options.out) {
+        eprintln!("couldn't create {}: {}", options.out.display(), error);
+        exit(1);
+    }
+
+    let mut rendered = false;
+    for part in options.parts(solution) {
+        let frames = match solution.render(&input, part, &params) {
+            Ok(frames) => frames,
+            Err(error) => {
+                eprintln!("day {} part {}: {}", day, part, error);
+                exit(1);
+            }
+        };
+        if frames.is_empty() {
+            continue;
+        }
+        rendered = true;
+
+        let name = format!("day{:02}-part{}", day, part);
+        if let Err(error) = write_frames(options, &name, &frames) {
+            eprintln!(
+                "couldn't write the frames of day {} part {}: {}",
+                day, part, error
+            );
+            exit(1);
+        }
+        let noun = if frames.len() == 1 { "frame" } else { "frames" };
+        println!(
+            "day {} part {}: {} {} in {}",
+            day,
+            part,
+            frames.len(),
+            noun,
+            options.out.display()
+        );
+    }
+
+    if !rendered {
+        eprintln!("day {} has nothing to render", day);
+        exit(1);
+    }
+}
+
+/// Writes an image for each frame, named `<name>-NNN.ppm` (or `.pbm`), and
+/// all of them as text in `<name>.txt`.
+fn write_frames(options: &Options, name: &str, frames: &[Frame]) -> io::Result<()> {
+    let extension = if options.pbm { "pbm" } else { "ppm" };
+    for (idx, frame) in frames.iter().enumerate() {
+        let path = options
+            .out
+            .join(format!("{}-{:03}.{}", name, idx, extension));
+        let mut out = BufWriter::new(File::create(path)?);
+        if options.pbm {
+            render::write_pbm(frame, options.scale, &mut out)?;
+        } else {
+            render::write_ppm(frame, options.scale, &mut out)?;
+        }
+        out.flush()?;
+    }
+
+    let mut out = BufWriter::new(File::create(options.out.join(format!("{}.txt", name)))?);
+    render::write_text(frames, &mut out)?;
+    out.flush()
+}
+
+fn serve(options: &Options) {
+    let server = match Server::bind(("127.0.0.1", options.port), options.timeout, options.jobs) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("couldn't listen on port {}: {}", options.port, error);
+            exit(1);
+        }
+    };
+    if let Ok(addr) = server.local_addr() {
+        eprintln!("listening on http://{}", addr);
+    }
+
+    if let Err(error) = server.run() {
+        eprintln!("the server stopped: {}", error);
+        exit(1);
+    }
+}
+
+fn repl(options: &Options) {
+    let rules_path = match &options.input {
+        Some(path) => path.clone(),
+        None => input::default_path(&options.inputs_dir, options.year().year, 19),
+    };
+
+    // only prompt a person, not a pipe
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    if let Err(error) = Repl::new(rules_path).run(stdin.lock(), &mut io::stdout(), prompt) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
+
+fn cache(options: &Options) {
+    match options.positional.first().map(String::as_str) {
+        Some("clear") => (),
+        Some(other) => usage_error(&format!("unknown cache command `{}`", other)),
+        None => usage_error("missing cache command"),
+    }
+
+    match Cache::new(options.cache_dir.clone()).clear() {
+        Ok(removed) => println!(
+            "removed {} answers from {}",
+            removed,
+            options.cache_dir.display()
+        ),
+        Err(error) => {
+            eprintln!("couldn't clear {}: {}", options.cache_dir.display(), error);
+            exit(1);
+        }
+    }
+}
+
+fn new(options: &Options) {
+    let day = match options.new_day {
+        Some(day) => day,
+        None => usage_error("new needs --day"),
+    };
+    let year = options.year.unwrap_or(solver::latest().year);
+
+    match scaffold::new_day(&PathBuf::new(), &options.inputs_dir, year, day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("wrote {}", path.display());
+            }
+        }
+        Err(error) => {
+            eprintln!("couldn't add day {} of {}: {}", day, year, error);
+            exit(1);
+        }
+    }
+}
+
+/// Starts the trace sink for `--trace`.
+fn start_trace(options: &Options) {
+    let out: Box<dyn Write + Send> = match &options.trace_file {
+        Some(path) => match File::create(path) {
+            // a line at a time, so that the trace survives the run being killed
+            Ok(file) => Box::new(LineWriter::new(file)),
+            Err(error) => {
+                eprintln!("couldn't create {}: {}", path.display(), error);
+                exit(1);
+            }
+        },
+        None => Box::new(io::stderr()),
+    };
+    trace::start(options.trace_kinds.clone(), out);
+
+    // the events just before a panic are the interesting ones
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        trace::flush();
+        hook(info);
+    }));
+}
+
+fn stop_trace() {
+    if let Err(error) = trace::stop() {
+        eprintln!("couldn't write the trace: {}", error);
+    }
+}
+
+/// Exits, after writing out the rest of the trace.
+fn exit(code: i32) -> ! {
+    stop_trace();
+    process::exit(code);
+}
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    match args[1].as_str() {
-        "day1a" => day1::day1(true),
-        "day1b" => day1::day1(false),
-        "day2a" => day2::day2(true),
-        "day2b" => day2::day2(false),
-        "day3a" => day3::day3(true),
-        "day3b" => day3::day3(false),
-        "day4a" => day4::day4(true),
-        "day4b" => day4::day4(false),
-        "day5a" => day5::day5(true),
-        "day5b" => day5::day5(false),
-        "day6a" => day6::day6(true),
-        "day6b" => day6::day6(false),
-        "day7a" => day7::day7(true),
-        "day7b" => day7::day7(false),
-        "day8a" => day8::day8(true),
-        "day8b" => day8::day8(false),
-        "day9" => day9::day9(),
-        "day10a" => day10::day10(true),
-        "day10b" => day10::day10(false),
-        "day11a" => day11::day11(true),
-        "day11b" => day11::day11(false),
-        "day12a" => day12::day12(true),
-        "day12b" => day12::day12(false),
-        "day13a" => day13::day13(true),
-        "day13b" => day13::day13(false),
-        "day14a" => day14::day14(true),
-        "day14b" => day14::day14(false),
-        "day15a" => day15::day15(true),
-        "day15b" => day15::day15(false),
-        "day16a" => day16::day16(true),
-        "day16b" => day16::day16(false),
-        "day17" => day17::day17(),
-        "day18a" => day18::day18(true),
-        "day18b" => day18::day18(false),
-        "day19a" => day19::day19(true),
-        "day19b" => day19::day19(false),
-        "day20a" => day20::day20(true),
-        "day20b" => day20::day20(false),
-        "day21" => day21::day21(),
-        "day22a" => day22::day22(true),
-        "day22b" => day22::day22(false),
-        "day23a" => day23::day23(true),
-        "day23b" => day23::day23(false),
-        "day24" => day24::day24(),
-        "day25" => day25::day25(),
-        _ => {
-            panic!("invalid day {}", args[1]);
+    let command = match args.get(1) {
+        Some(command) => command.as_str(),
+        None => usage_error("missing command"),
+    };
+    let options = Options::parse(&args[2..]);
+    // the solvers' output would only get in the way of the timings, and
+    // generators use the solvers to check their inputs; the server solves
+    // several things at once, which would make a mess of it; check only
+    // wants the problems with the inputs
+    log::set_enabled(
+        !options.quiet && !matches!(command, "bench" | "generate" | "serve" | "check"),
+    );
+    if options.format == Format::Json && !matches!(command, "day" | "all") {
+        usage_error("--format json only works with day and all");
+    }
+    if !options.params.is_empty() && !matches!(command, "day" | "bench" | "render" | "generate") {
+        usage_error("--param only works with day, bench, render and generate");
+    }
+    if options.trace {
+        if !matches!(command, "day" | "all") {
+            usage_error("--trace only works with day and all");
         }
+        start_trace(&options);
+    }
+    if options.no_cache && !matches!(command, "all" | "verify") {
+        usage_error("--no-cache only works with all and verify");
+    }
+    if let Some(dir) = &options.checkpoint {
+        if command != "day" {
+            usage_error("--checkpoint only works with day");
+        }
+        checkpoint::enable(dir.clone(), options.checkpoint_every);
+    }
+
+    match command {
+        "list" => list(&options),
+        "new" => new(&options),
+        "cache" => cache(&options),
+        "day" => day(&options),
+        "all" => all(&options),
+        "verify" => verify(&options),
+        "check" => check(&options),
+        "bench" => bench(&options),
+        "generate" => generate(&options),
+        "render" => render(&options),
+        "serve" => serve(&options),
+        "repl" => repl(&options),
+        _ => usage_error(&format!("unknown command `{}`", command)),
     }
+    stop_trace();
 }
@@ -0,0 +1,148 @@
+//! Named puzzle parameters, like the number of rounds of a game, that can be
+//! changed from their defaults to try out variants of a puzzle.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::solver::Part;
+
+/// A parameter that a day declares, with its default value.
+pub struct Param {
+    pub name: &'static str,
+    /// The part it applies to, or `None` for both. A parameter can be declared
+    /// once for each part when the parts have different defaults.
+    pub part: Option<Part>,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl Param {
+    fn applies_to(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Parameter values by name: either the overrides given by the user, or all
+/// of a part's parameters once they've been resolved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    /// Sets a parameter from a `key=value` string.
+    pub fn set_assignment(&mut self, assignment: &str) -> Result<(), String> {
+        match assignment.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                self.set(key.trim(), value.trim());
+                Ok(())
+            }
+            _ => Err(format!("expected `key=value`, found `{}`", assignment)),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The names and values, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Checks that every override is declared by the day.
+    pub fn check(&self, day: u32, declared: &[Param]) -> Result<(), Error> {
+        for key in self.values.keys() {
+            if !declared.iter().any(|param| param.name == key) {
+                return Err(Error::InvalidParam(format!(
+                    "day {} has no parameter `{}`",
+                    day, key
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The values of the part's parameters: the defaults, with the overrides
+    /// applied.
+    pub fn resolve(&self, day: u32, declared: &[Param], part: Part) -> Result<Params, Error> {
+        self.check(day, declared)?;
+
+        let mut resolved = Params::new();
+        for param in declared.iter().filter(|param| param.applies_to(part)) {
+            let value = self.values.get(param.name).map_or(param.default, |v| v);
+            resolved.set(param.name, value);
+        }
+
+        Ok(resolved)
+    }
+
+    /// Parses the value of a parameter.
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<T, Error> {
+        let value = match self.values.get(key) {
+            Some(value) => value,
+            None => return Err(Error::InvalidParam(format!("no parameter `{}`", key))),
+        };
+
+        value
+            .parse()
+            .map_err(|_| Error::InvalidParam(format!("invalid value `{}` for {}", value, key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "turns",
+            part: Some(Part::One),
+            default: "2020",
+            help: "",
+        },
+        Param {
+            name: "turns",
+            part: Some(Part::Two),
+            default: "30000000",
+            help: "",
+        },
+        Param {
+            name: "size",
+            part: None,
+            default: "9",
+            help: "",
+        },
+    ];
+
+    #[test]
+    fn resolves_defaults_and_overrides() {
+        let mut overrides = Params::new();
+        overrides.set_assignment("size = 12").unwrap();
+
+        let params = overrides.resolve(1, PARAMS, Part::Two).unwrap();
+        assert_eq!(params.get::<usize>("turns"), Ok(30000000));
+        assert_eq!(params.get::<usize>("size"), Ok(12));
+
+        overrides.set("turns", "ten");
+        let params = overrides.resolve(1, PARAMS, Part::One).unwrap();
+        assert!(matches!(
+            params.get::<usize>("turns"),
+            Err(Error::InvalidParam(_))
+        ));
+
+        overrides.set("speed", "1");
+        assert!(overrides.resolve(1, PARAMS, Part::One).is_err());
+        assert!(Params::new().set_assignment("size").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Helpers for parsing puzzle inputs into errors that point at the bad text,
+//! and for splitting inputs into blank-line-separated paragraphs and sections.
+
+use std::str::FromStr;
+
+use crate::error::{Error, ParseError};
+
+/// A line of the input, along with its line number (starting from 1).
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// The lines of the input, numbered.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(idx, text)| Line {
+        number: idx + 1,
+        text,
+    })
+}
+
+/// Parses each line on its own, and returns every error instead of stopping
+/// at the first.
+pub fn line_errors<'a, T>(
+    input: &'a str,
+    parse_line: impl FnMut(Line<'a>) -> Result<T, Error>,
+) -> Vec<Error> {
+    lines(input)
+        .map(parse_line)
+        .filter_map(Result::err)
+        .collect()
+}
+
+/// An error for input that stops before it should.
+pub fn end_of_input(input: &str, message: impl Into<String>) -> Error {
+    Error::Parse(ParseError {
+        day: None,
+        section: None,
+        line: input.lines().count() + 1,
+        column: 1,
+        source: String::new(),
+        text: String::new(),
+        message: message.into(),
+    })
+}
+
+impl<'a> Line<'a> {
+    /// An error about `part` of the line.
+    ///
+    /// `part` should be a slice of the line, so that the column is exact; for
+    /// other strings the first occurrence in the line is blamed, or failing
+    /// that the whole line.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> Error {
+        let start = self.text.as_ptr() as usize;
+        let part_start = part.as_ptr() as usize;
+        let offset = if part_start >= start && part_start + part.len() <= start + self.text.len() {
+            Some(part_start - start)
+        } else {
+            self.text.find(part)
+        };
+
+        let (column, text) = match offset {
+            Some(offset) => (self.text[..offset].chars().count() + 1, part),
+            None => (1, self.text),
+        };
+
+        Error::Parse(ParseError {
+            day: None,
+            section: None,
+            line: self.number,
+            column,
+            source: self.text.to_string(),
+            text: text.to_string(),
+            message: message.into(),
+        })
+    }
+
+    /// Parses a number from `part` of the line.
+    pub fn parse<T: FromStr>(&self, part: &str) -> Result<T, Error> {
+        let part = part.trim();
+        part.parse::<T>()
+            .map_err(|_| self.error(part, format!("expected a number, found `{}`", part)))
+    }
+
+    /// Splits the line in two at the first `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(&'a str, &'a str), Error> {
+        match self.text.find(separator) {
+            Some(idx) => Ok((&self.text[..idx], &self.text[idx + separator.len()..])),
+            None => Err(self.error(self.text, format!("expected `{}`", separator))),
+        }
+    }
+}
+
+/// A run of non-blank lines, separated from the rest of the input by blank
+/// lines.
+#[derive(Clone, Debug)]
+pub struct Paragraph<'a> {
+    /// What the paragraph holds, for error messages: the header of a named
+    /// section (without the colon), or a description given when reading it.
+    pub name: Option<String>,
+    /// The header line of a named section, like `your ticket:`.
+    pub header: Option<Line<'a>>,
+    /// The lines after the header, if any.
+    pub lines: Vec<Line<'a>>,
+}
+
+/// The paragraphs of the input. Any number of blank (or whitespace-only)
+/// lines separate them, and there may be some at the start or end.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = Paragraph<'_>> {
+    let mut lines = lines(input).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.text.trim().is_empty()).is_some() {}
+
+        let mut paragraph = Vec::new();
+        while let Some(line) = lines.next_if(|line| !line.text.trim().is_empty()) {
+            paragraph.push(line);
+        }
+
+        if paragraph.is_empty() {
+            None
+        } else {
+            Some(Paragraph {
+                name: None,
+                header: None,
+                lines: paragraph,
+            })
+        }
+    })
+}
+
+impl<'a> Paragraph<'a> {
+    /// The text of the lines, each ending in a newline.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| format!("{}\n", line.text))
+            .collect()
+    }
+
+    /// Parses each line into a record, noting the paragraph's name in any
+    /// error.
+    pub fn records<T>(
+        &self,
+        mut parse_line: impl FnMut(Line<'a>) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        self.lines
+            .iter()
+            .map(|line| parse_line(*line))
+            .collect::<Result<_, _>>()
+            .map_err(|error| self.in_section(error))
+    }
+
+    /// Notes the paragraph's name in an error about it.
+    pub fn in_section(&self, error: Error) -> Error {
+        match &self.name {
+            Some(name) => error.in_section(name),
+            None => error,
+        }
+    }
+}
+
+/// Reads the paragraphs of an input in order, expecting each to be a
+/// particular section.
+pub struct Sections<'a> {
+    input: &'a str,
+    paragraphs: std::vec::IntoIter<Paragraph<'a>>,
+}
+
+/// The sections of the input, to be read in order.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        input,
+        paragraphs: paragraphs(input).collect::<Vec<_>>().into_iter(),
+    }
+}
+
+impl<'a> Sections<'a> {
+    /// The next paragraph, which is called `name` in errors.
+    pub fn unnamed(&mut self, name: &str) -> Result<Paragraph<'a>, Error> {
+        match self.paragraphs.next() {
+            Some(paragraph) => Ok(Paragraph {
+                name: Some(name.to_string()),
+                ..paragraph
+            }),
+            None => Err(end_of_input(self.input, format!("expected {}", name))),
+        }
+    }
+
+    /// The next paragraph, which must start with the header line `header`,
+    /// e.g. `Player 1:`.
+    pub fn named(&mut self, header: &str) -> Result<Paragraph<'a>, Error> {
+        let paragraph = match self.paragraphs.next() {
+            Some(paragraph) => paragraph,
+            None => return Err(end_of_input(self.input, format!("expected `{}`", header))),
+        };
+
+        let first = paragraph.lines[0];
+        if first.text.trim() != header {
+            return Err(first.error(first.text, format!("expected `{}`", header)));
+        }
+
+        Ok(Paragraph {
+            name: Some(header.trim_end_matches(':').to_string()),
+            header: Some(first),
+            lines: paragraph.lines[1..].to_vec(),
+        })
+    }
+
+    /// Checks that there are no sections left.
+    pub fn finish(mut self) -> Result<(), Error> {
+        match self.paragraphs.next() {
+            Some(paragraph) => {
+                let first = paragraph.lines[0];
+                Err(first.error(first.text, "expected the end of the input"))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Paragraph<'a>;
+
+    /// The next paragraph, if there is one.
+    fn next(&mut self) -> Option<Paragraph<'a>> {
+        self.paragraphs.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_points_at_part() {
+        let line = Line {
+            number: 3,
+            text: "acc +1 +1",
+        };
+        let error = match line.error(&line.text[7..], "unexpected argument") {
+            Error::Parse(error) => error,
+            error => panic!("unexpected error {:?}", error),
+        };
+        assert_eq!((error.line, error.column), (3, 8));
+        assert_eq!(error.text, "+1");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 8: unexpected argument\n  3 | acc +1 +1\n    |        ^^"
+        );
+    }
+
+    #[test]
+    fn error_finds_copied_part() {
+        let line = Line {
+            number: 1,
+            text: "a b c",
+        };
+        match line.error("c", "bad") {
+            Error::Parse(error) => assert_eq!(error.column, 5),
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn splits_paragraphs() {
+        let input = "\n\na\nb\n  \n\nc\n\n";
+        let paragraphs: Vec<_> = paragraphs(input).map(|p| p.text()).collect();
+        assert_eq!(paragraphs, ["a\nb\n", "c\n"]);
+    }
+
+    #[test]
+    fn named_sections() {
+        let input = "1,2\n\nyour ticket:\n3\nx\n\nextra\n";
+        let mut sections = sections(input);
+        assert_eq!(sections.unnamed("rules").unwrap().lines.len(), 1);
+
+        let ticket = sections.named("your ticket:").unwrap();
+        assert_eq!(ticket.header.map(|line| line.number), Some(3));
+        let error = match ticket.records(|line| line.parse::<u32>(line.text)) {
+            Err(Error::Parse(error)) => error,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(
+            (error.line, error.section.as_deref()),
+            (5, Some("your ticket"))
+        );
+        assert!(error
+            .to_string()
+            .starts_with("line 5, column 1 (your ticket): "));
+
+        assert!(sections.named("nearby tickets:").is_err());
+        assert!(sections.finish().is_ok());
+        assert!(super::sections("a\n\nb\n").finish().is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        let line = Line {
+            number: 1,
+            text: "12 x3",
+        };
+        assert_eq!(line.parse::<u32>(&line.text[..2]), Ok(12));
+        assert!(line.parse::<u32>(&line.text[3..]).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Running jobs on a pool of threads, keeping going when one of them panics.
+//!
+//! `map` runs a batch of jobs and waits for them all; a `Pool` keeps its
+//! threads around, and takes jobs as they come.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// The number of threads to use when none is given: one per core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Calls `f` on every item, spread over `threads` threads, and returns the
+/// results in the same order as the items.
+///
+/// A job that panics gives `Err` with the panic message instead of bringing
+/// down the others. The panic is still reported by the panic hook, so
+/// callers that report it themselves may want to silence that.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<R, String>>>> =
+        Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(idx) {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(panic_message);
+                results.lock().unwrap()[idx] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A fixed number of threads, running jobs in the order they're given, with
+/// at most so many waiting for a thread at once.
+pub struct Pool {
+    sender: Option<SyncSender<Job>>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl Pool {
+    /// Starts `threads` threads, with room for `queue` jobs to wait for one.
+    pub fn new(threads: usize, queue: usize) -> Pool {
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let threads = (0..threads.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || work(&receiver))
+            })
+            .collect();
+
+        Pool {
+            sender: Some(sender),
+            threads,
+        }
+    }
+
+    /// Queues the job, returning whether there was room for it. A job that
+    /// panics doesn't take its thread down with it.
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) -> bool {
+        let sender = self.sender.as_ref().unwrap();
+        match sender.try_send(Box::new(job)) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+impl Drop for Pool {
+    /// Waits for the jobs already queued to finish.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Runs jobs until the pool is gone.
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock().unwrap().recv() {
+            Ok(job) => job,
+            Err(_) => break,
+        };
+        let _ = panic::catch_unwind(AssertUnwindSafe(job));
+    }
+}
+
+/// The message a panic was started with, if it was a string.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_catches_panics() {
+        let items: Vec<u32> = (0..20).collect();
+        let results = map(&items, 4, |&n| {
+            if n == 7 {
+                panic!("unlucky {}", n);
+            }
+            n * 2
+        });
+
+        assert_eq!(results.len(), 20);
+        assert_eq!(results[3], Ok(6));
+        assert_eq!(results[7], Err("unlucky 7".to_string()));
+        assert_eq!(results[19], Ok(38));
+    }
+
+    #[test]
+    fn pool_runs_jobs_until_full() {
+        let (sender, receiver) = mpsc::channel();
+        let (release, blocked) = mpsc::channel::<()>();
+        let blocked = Mutex::new(blocked);
+        let pool = Pool::new(1, 1);
+
+        // one job on the thread, and one waiting
+        let started = sender.clone();
+        assert!(pool.execute(move || {
+            started.send(0).unwrap();
+            let _ = blocked.lock().unwrap().recv();
+            panic!("the thread carries on");
+        }));
+        assert_eq!(receiver.recv(), Ok(0));
+        let done = sender.clone();
+        assert!(pool.execute(move || done.send(1).unwrap()));
+        assert!(!pool.execute(|| ()));
+
+        release.send(()).unwrap();
+        assert_eq!(receiver.recv(), Ok(1));
+        let done = sender.clone();
+        assert!(pool.execute(move || done.send(2).unwrap()));
+        drop(pool);
+        assert_eq!(receiver.recv(), Ok(2));
+    }
+}
@@ -0,0 +1,72 @@
+//! Progress reports for the solvers that take a while.
+
+use std::time::{Duration, Instant};
+
+use crate::cancel::{self, Cancel};
+use crate::error::Error;
+use crate::log;
+
+/// How often to look at the clock, and whether to stop, in steps.
+const CHECK_EVERY: usize = 1 << 16;
+
+/// How often to report.
+const REPORT_EVERY: Duration = Duration::from_secs(1);
+
+/// Reports how far through a long loop a solver is, and about how long is
+/// left, at most once a second. It also stops the loop if the solver is
+/// cancelled.
+pub struct Progress {
+    /// What a step is, e.g. "turn".
+    step: &'static str,
+    total: usize,
+    /// The step it started from, which isn't 0 after resuming.
+    first: usize,
+    start: Instant,
+    last_report: Instant,
+    cancel: Option<Cancel>,
+}
+
+impl Progress {
+    pub fn new(step: &'static str, first: usize, total: usize) -> Progress {
+        let now = Instant::now();
+        Progress {
+            step,
+            total,
+            first,
+            start: now,
+            last_report: now,
+            cancel: cancel::current(),
+        }
+    }
+
+    /// Notes that `done` steps are done, or gives `Error::Cancelled` if the
+    /// loop should stop. This is cheap enough to call every step.
+    pub fn update(&mut self, done: usize) -> Result<(), Error> {
+        if !done.is_multiple_of(CHECK_EVERY) {
+            return Ok(());
+        }
+        if self.cancel.as_ref().is_some_and(Cancel::is_cancelled) {
+            return Err(Error::Cancelled);
+        }
+        if !log::enabled() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if now - self.last_report < REPORT_EVERY {
+            return Ok(());
+        }
+        self.last_report = now;
+
+        let rate = (done - self.first) as f64 / (now - self.start).as_secs_f64();
+        let left = self.total.saturating_sub(done) as f64 / rate;
+        log!(
+            "{} {} of {} ({:.0}%), about {:.0}s left",
+            self.step,
+            done,
+            self.total,
+            100.0 * done as f64 / self.total as f64,
+            left
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+//! Pictures of the days that play out on a map, one frame per generation, to
+//! be strung together into animations.
+//!
+//! A frame is a grid of characters, the same ones the puzzles use (`#` for
+//! something alive or occupied, `.` for empty, and so on), so it can be dumped
+//! as text or drawn as an image with a colour per character.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use crate::grid::Grid;
+
+pub type Frame = Grid<char>;
+
+pub type Colour = [u8; 3];
+
+/// The colour each character is drawn with. Spaces, and anything else, are
+/// background.
+pub fn colour(c: char) -> Colour {
+    match c {
+        '#' => [32, 32, 32],
+        '.' => [224, 224, 224],
+        'L' => [120, 170, 220],
+        'O' => [220, 40, 40],
+        _ => [255, 255, 255],
+    }
+}
+
+/// Writes the frame as a binary PPM image, with each cell drawn as a square
+/// of `scale` by `scale` pixels.
+pub fn write_ppm(frame: &Frame, scale: usize, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+
+    let mut row_pixels = Vec::with_capacity(width * 3);
+    for row in frame.rows() {
+        row_pixels.clear();
+        for c in row {
+            for _ in 0..scale {
+                row_pixels.extend_from_slice(&colour(*c));
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&row_pixels)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the frame as a binary PBM image, where a cell is black if its
+/// colour is closer to black than to white. If the frame has anything picked
+/// out with `O`s, like the sea monsters, those are the only black cells, as
+/// they'd be lost among the `#`s otherwise.
+pub fn write_pbm(frame: &Frame, scale: usize, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    write!(out, "P4\n{} {}\n", width, height)?;
+
+    let highlighted = frame.count(|c| *c == 'O') > 0;
+    let is_dark = |c: char| {
+        if highlighted {
+            return c == 'O';
+        }
+        let [r, g, b] = colour(c);
+        let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+        luma < 128_000
+    };
+
+    // 8 pixels to a byte, with each row padded to a whole byte
+    let mut row_bits = vec![0u8; width.div_ceil(8)];
+    for row in frame.rows() {
+        row_bits.iter_mut().for_each(|byte| *byte = 0);
+        for (col, c) in row.iter().enumerate() {
+            if is_dark(*c) {
+                for x in col * scale..(col + 1) * scale {
+                    row_bits[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&row_bits)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes every frame as text, each after a line with its number.
+pub fn write_text(frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+    for (idx, frame) in frames.iter().enumerate() {
+        writeln!(out, "frame {}", idx)?;
+        write!(out, "{}", frame.render(|c| *c))?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Draws the generations of an automaton on a lattice: each frame shows the
+/// `x`/`y` slices side by side, one for each `z`, with a row of them for each
+/// `w` in 4 dimensions. Every frame covers the same space, big enough for
+/// all of the generations.
+pub fn lattice_frames<const N: usize>(history: &[HashSet<[i64; N]>]) -> Vec<Frame> {
+    assert!((2..=4).contains(&N), "can only draw 2 to 4 dimensions");
+
+    let cells = || history.iter().flatten();
+    let mut min = [0; N];
+    let mut max = [0; N];
+    for dim in 0..N {
+        min[dim] = cells().map(|cell| cell[dim]).min().unwrap_or(0);
+        max[dim] = cells().map(|cell| cell[dim]).max().unwrap_or(0);
+    }
+
+    let extent = |dim: usize| match dim < N {
+        true => (max[dim] - min[dim] + 1) as usize,
+        false => 1,
+    };
+    let offset = |cell: &[i64; N], dim: usize| match dim < N {
+        true => (cell[dim] - min[dim]) as usize,
+        false => 0,
+    };
+
+    // a blank column or row between slices
+    let (slice_width, slice_height) = (extent(0) + 1, extent(1) + 1);
+    let width = slice_width * extent(2) - 1;
+    let height = slice_height * extent(3) - 1;
+
+    history
+        .iter()
+        .map(|live| {
+            let mut frame = Grid::new(width, height, ' ');
+            for z in 0..extent(2) {
+                for w in 0..extent(3) {
+                    for y in 0..extent(1) {
+                        for x in 0..extent(0) {
+                            frame[(w * slice_height + y, z * slice_width + x)] = '.';
+                        }
+                    }
+                }
+            }
+            for cell in live {
+                let row = offset(cell, 3) * slice_height + offset(cell, 1);
+                let col = offset(cell, 2) * slice_width + offset(cell, 0);
+                frame[(row, col)] = '#';
+            }
+            frame
+        })
+        .collect()
+}
+
+/// Draws the generations of an automaton on hexagons (in the axial
+/// coordinates of [`Hex`](crate::automaton::Hex)), as a wall of bricks two
+/// characters wide, with each row offset by half a brick from the one below.
+/// That way each brick touches the same six bricks as its hexagon does.
+pub fn hex_frames(history: &[HashSet<(i32, i32)>]) -> Vec<Frame> {
+    // the left half of a hexagon's brick, and its row, with north up
+    let brick = |(x, y): (i32, i32)| (2 * x + y, -y);
+
+    let bricks = || history.iter().flatten().map(|cell| brick(*cell));
+    let min_col = bricks().map(|(col, _)| col).min().unwrap_or(0);
+    let max_col = bricks().map(|(col, _)| col).max().unwrap_or(0);
+    let min_row = bricks().map(|(_, row)| row).min().unwrap_or(0);
+    let max_row = bricks().map(|(_, row)| row).max().unwrap_or(0);
+
+    let width = (max_col - min_col + 2) as usize;
+    let height = (max_row - min_row + 1) as usize;
+
+    let mut blank = Grid::new(width, height, ' ');
+    for row in 0..height {
+        // the bricks in a row all start on odd or all on even columns
+        let start = (row as i32 + min_row + min_col).rem_euclid(2) as usize;
+        for col in (start..width - 1).step_by(2) {
+            blank[(row, col)] = '.';
+            blank[(row, col + 1)] = '.';
+        }
+    }
+
+    history
+        .iter()
+        .map(|live| {
+            let mut frame = blank.clone();
+            for cell in live {
+                let (col, row) = brick(*cell);
+                let (col, row) = ((col - min_col) as usize, (row - min_row) as usize);
+                frame[(row, col)] = '#';
+                frame[(row, col + 1)] = '#';
+            }
+            frame
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hexagons_are_offset_bricks() {
+        let live: HashSet<_> = vec![(0, 0), (1, 0), (0, 1)].into_iter().collect();
+        let frames = hex_frames(&[live, HashSet::new()]);
+        assert_eq!(frames[0].render(|c| *c), " ## \n####\n");
+        assert_eq!(frames[1].render(|c| *c), " .. \n....\n");
+    }
+
+    #[test]
+    fn slices_side_by_side() {
+        let live: HashSet<_> = vec![[0, 0, 0], [1, 1, 1]].into_iter().collect();
+        let frames = lattice_frames(&[live]);
+        assert_eq!(frames[0].render(|c| *c), "#. ..\n.. .#\n");
+    }
+
+    #[test]
+    fn images() {
+        let frame = Grid::from_rows(vec![vec!['#', '.', 'O']]);
+
+        let mut ppm = Vec::new();
+        write_ppm(&frame, 2, &mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n6 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 6 * 2 * 3);
+
+        let mut pbm = Vec::new();
+        write_pbm(&frame, 3, &mut pbm).unwrap();
+        assert_eq!(pbm, b"P4\n9 3\n\x03\x80\x03\x80\x03\x80");
+
+        // without anything highlighted, `#` is the dark one
+        let frame = Grid::from_rows(vec![vec!['#', '.', 'L']]);
+        let mut pbm = Vec::new();
+        write_pbm(&frame, 3, &mut pbm).unwrap();
+        assert_eq!(pbm, b"P4\n9 3\n\xe0\x00\xe0\x00\xe0\x00");
+    }
+}
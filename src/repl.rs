@@ -0,0 +1,313 @@
+//! An interactive prompt for trying out single lines of puzzle input against
+//! the days' parsers and checkers, without writing a whole input each time.
+//!
+//! Each command is one day's idea of a line:
+//!
+//! ```text
+//! > seat FBFBBFFRLR
+//! row 44, column 5, seat id 357
+//! > match 0 ababbb
+//! rule 0 matches
+//! ```
+//!
+//! Context a command needs, like day 19's rules, is loaded the first time it
+//! is used and kept for the rest of the session. Lines are kept in a history,
+//! which `history` lists and `!n` (or `!!` for the last one) runs again.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use crate::input;
+use crate::parse::Line;
+use crate::solver::Solver;
+use crate::y2020::day18::{self, Precedence};
+use crate::y2020::day19::{self, Day19, Rules};
+use crate::y2020::{day05, day24};
+
+pub const HELP: &str = "\
+commands:
+    seat <pass>             decode a boarding pass, e.g. `seat FBFBBFFRLR` (day 5)
+    expr <expression>       evaluate homework with both precedences, e.g. `expr 1 + (2 * 3)` (day 18)
+    hexpath <directions>    follow directions to a tile, e.g. `hexpath nwwswee` (day 24)
+    match <rule> <message>  check a message against a rule, e.g. `match 0 ababbb` (day 19)
+    load <path>             read day 19's rules from another file
+    history                 list the lines so far; `!n` runs line n again, `!!` the last one
+    help                    show this
+    quit                    stop (so does the end of input)";
+
+/// What a line asks for.
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Print(String),
+    Quit,
+}
+
+/// A session: the loaded context and the history.
+pub struct Repl {
+    /// Where day 19's rules are read from.
+    rules_path: PathBuf,
+    rules: Option<Rules>,
+    history: Vec<String>,
+}
+
+impl Repl {
+    pub fn new(rules_path: PathBuf) -> Repl {
+        Repl {
+            rules_path,
+            rules: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Reads commands from `input` until it ends or says to quit, printing
+    /// each reply to `out`, and errors after `error: `. The prompt is only
+    /// shown if `prompt` is set, so that piped commands give clean output.
+    pub fn run(
+        &mut self,
+        input: impl BufRead,
+        out: &mut impl Write,
+        prompt: bool,
+    ) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            if prompt {
+                write!(out, "> ")?;
+                out.flush()?;
+            }
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => break,
+            };
+
+            let line = match self.recall(line.trim()) {
+                Ok(line) => line,
+                Err(message) => {
+                    writeln!(out, "error: {}", message)?;
+                    continue;
+                }
+            };
+            if line.is_empty() {
+                continue;
+            }
+            if line != self.history.last().map_or("", String::as_str) {
+                self.history.push(line.clone());
+            }
+
+            match self.eval(&line) {
+                Ok(Reply::Print(text)) => writeln!(out, "{}", text)?,
+                Ok(Reply::Quit) => break,
+                Err(message) => writeln!(out, "error: {}", message)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces a `!n` or `!!` line with the line from the history.
+    fn recall(&self, line: &str) -> Result<String, String> {
+        let entry = match line.strip_prefix('!') {
+            Some("!") => self.history.len(),
+            Some(n) => n
+                .parse()
+                .map_err(|_| format!("expected `!!` or `!<n>`, not `{}`", line))?,
+            None => return Ok(line.to_string()),
+        };
+
+        match entry.checked_sub(1).and_then(|idx| self.history.get(idx)) {
+            Some(line) => Ok(line.clone()),
+            None => Err(format!("there is no line {} in the history", entry)),
+        }
+    }
+
+    /// Runs a command, without touching the history.
+    pub fn eval(&mut self, line: &str) -> Result<Reply, String> {
+        let line = line.trim();
+        let (command, rest) = match line.split_once(char::is_whitespace) {
+            Some((command, rest)) => (command, rest.trim()),
+            None => (line, ""),
+        };
+        // errors point at the argument as if it were the first line of an input
+        let query = Line {
+            number: 1,
+            text: rest,
+        };
+
+        let reply = match command {
+            "seat" => {
+                let id = day05::parse_seat(query).map_err(|error| error.to_string())?;
+                format!("row {}, column {}, seat id {}", id / 8, id % 8, id)
+            }
+            "expr" => {
+                let tokens = day18::parse_expression(query).map_err(|error| error.to_string())?;
+                format!(
+                    "left to right: {}, addition first: {}",
+                    day18::eval_expression(&tokens, Precedence::Equal),
+                    day18::eval_expression(&tokens, Precedence::AddFirst)
+                )
+            }
+            "hexpath" => {
+                let (x, y) = day24::get_coords(query).map_err(|error| error.to_string())?;
+                let steps = x.abs().max(y.abs()).max((x + y).abs());
+                let plural = if steps == 1 { "" } else { "s" };
+                format!("tile ({}, {}), {} step{} away", x, y, steps, plural)
+            }
+            "match" => self.check_match(rest)?,
+            "load" => {
+                if rest.is_empty() {
+                    return Err("load needs a path".to_string());
+                }
+                self.rules_path = PathBuf::from(rest);
+                self.rules = None;
+                let count = self.rules()?.len();
+                format!("read {} rules from {}", count, self.rules_path.display())
+            }
+            "history" => self
+                .history
+                .iter()
+                .enumerate()
+                .map(|(idx, line)| format!("{:>4}  {}", idx + 1, line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "help" => HELP.to_string(),
+            "quit" | "exit" => return Ok(Reply::Quit),
+            _ => return Err(format!("unknown command `{}`; try `help`", command)),
+        };
+
+        Ok(Reply::Print(reply))
+    }
+
+    /// Day 19's rules, read the first time they're needed.
+    fn rules(&mut self) -> Result<&Rules, String> {
+        if self.rules.is_none() {
+            let path = &self.rules_path;
+            let text = input::read(path)
+                .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+            let puzzle =
+                Day19::parse(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+            self.rules = Some(puzzle.rules);
+        }
+        Ok(self.rules.as_ref().unwrap())
+    }
+
+    fn check_match(&mut self, args: &str) -> Result<String, String> {
+        let (rule, message) = match args.split_once(char::is_whitespace) {
+            Some((rule, message)) => (rule, message.trim()),
+            None => return Err("usage: match <rule> <message>".to_string()),
+        };
+        let rule: u32 = rule
+            .parse()
+            .map_err(|_| format!("expected a rule number, not `{}`", rule))?;
+
+        let rules = self.rules()?;
+        if !rules.contains_key(&rule) {
+            return Err(format!("there is no rule {}", rule));
+        }
+
+        let verdict = |matches: bool| if matches { "matches" } else { "doesn't match" };
+        let end = day19::validate_rule(message, rules, &rule, 0);
+        let mut reply = format!("rule {} {}", rule, verdict(end == Some(message.len())));
+        if let Some(end) = end.filter(|&end| end < message.len()) {
+            reply.push_str(&format!(" (only the first {} characters)", end));
+        }
+
+        // part two's loops only change what rule 0 matches
+        if rule == 0 && rules.contains_key(&42) && rules.contains_key(&31) {
+            let matches = day19::is_valid_partb(message, rules);
+            reply.push_str(&format!(", and {} with the loops", verdict(matches)));
+        }
+
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn eval(repl: &mut Repl, line: &str) -> String {
+        match repl.eval(line) {
+            Ok(Reply::Print(text)) => text,
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn commands() {
+        let mut repl = Repl::new(PathBuf::from("no-such-file"));
+        assert_eq!(
+            eval(&mut repl, "seat FBFBBFFRLR"),
+            "row 44, column 5, seat id 357"
+        );
+        assert_eq!(
+            eval(&mut repl, "expr 1 + (2 * 3) + (4 * (5 + 6))"),
+            "left to right: 51, addition first: 51"
+        );
+        assert_eq!(
+            eval(&mut repl, "expr 2 * 3 + (4 * 5)"),
+            "left to right: 26, addition first: 46"
+        );
+        assert_eq!(
+            eval(&mut repl, "hexpath nwwswee"),
+            "tile (0, 0), 0 steps away"
+        );
+        assert_eq!(eval(&mut repl, "hexpath esew"), "tile (1, -1), 1 step away");
+
+        assert!(repl
+            .eval("seat FBFBBFFRLX")
+            .unwrap_err()
+            .contains("expected `L` or `R`"));
+        assert!(repl.eval("match 0 a").unwrap_err().contains("no-such-file"));
+        assert_eq!(repl.eval("quit"), Ok(Reply::Quit));
+    }
+
+    #[test]
+    fn loads_rules_once() {
+        let path = std::env::temp_dir().join(format!("aoc-repl-{}.txt", std::process::id()));
+        fs::write(&path, Day19::EXAMPLES[1].input).unwrap();
+        let mut repl = Repl::new(path.clone());
+
+        assert_eq!(
+            eval(&mut repl, "match 0 bbabbbbaabaabba"),
+            "rule 0 matches, and matches with the loops"
+        );
+        // the rules stay loaded without the file
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            eval(&mut repl, "match 0 babbbbaabbbbbabbbbbbaabaaabaaa"),
+            "rule 0 doesn't match, and matches with the loops"
+        );
+        assert_eq!(
+            eval(&mut repl, "match 42 babbbbaa"),
+            "rule 42 doesn't match (only the first 5 characters)"
+        );
+        assert_eq!(
+            repl.eval("match 99 a"),
+            Err("there is no rule 99".to_string())
+        );
+    }
+
+    #[test]
+    fn history() {
+        let input = "seat BFFFBBFRRR\n\n!!\nhexpath e\n!1\n!9\nhistory\nquit\nseat FFFBBBFRRR\n";
+        let mut out = Vec::new();
+        Repl::new(PathBuf::new())
+            .run(input.as_bytes(), &mut out, false)
+            .unwrap();
+
+        let seat = "row 70, column 7, seat id 567";
+        let tile = "tile (1, 0), 1 step away";
+        let history =
+            "   1  seat BFFFBBFRRR\n   2  hexpath e\n   3  seat BFFFBBFRRR\n   4  history";
+        let expected = [
+            seat,
+            seat,
+            tile,
+            seat,
+            "error: there is no line 9 in the history",
+            history,
+        ];
+        assert_eq!(String::from_utf8(out).unwrap(), expected.join("\n") + "\n");
+    }
+}
@@ -0,0 +1,108 @@
+//! A small seeded random number generator, so that generated inputs are the
+//! same every time for the same seed.
+
+#[cfg(test)]
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// SplitMix64: fast, with good enough randomness for making up inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to (but not including) `n`, which must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "no numbers below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in the range.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        let n = (end - start) as u128 + 1;
+        start + ((self.next_u64() as u128 * n) >> 64) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// One of the items, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Puts the items in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+/// Checks that two implementations of the same thing agree on random inputs,
+/// panicking with the first input they disagree on.
+///
+/// Each case gets its own seed, and a size for `generate` that grows from 1
+/// to `max_size` as the cases go on, so a disagreement usually turns up on a
+/// small input first.
+#[cfg(test)]
+pub fn check_agreement<I: Debug, O: Debug + PartialEq>(
+    cases: u64,
+    max_size: usize,
+    generate: impl Fn(&mut Rng, usize) -> I,
+    expected: impl Fn(&I) -> O,
+    actual: impl Fn(&I) -> O,
+) {
+    for seed in 0..cases {
+        let size = 1 + seed as usize * max_size / cases as usize;
+        let input = generate(&mut Rng::new(seed), size);
+        let (expected, actual) = (expected(&input), actual(&input));
+        assert!(
+            expected == actual,
+            "disagreement on seed {} (size {}): {:?} vs {:?} for {:?}",
+            seed,
+            size,
+            expected,
+            actual,
+            input
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((-2..=2).contains(&rng.range(-2..=2)));
+        }
+    }
+}
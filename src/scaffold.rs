@@ -0,0 +1,295 @@
+//! Adding a new day: a module with a skeleton `Solver`, registered with its
+//! year (and the year with the crate, if it's a new one), and an empty input
+//! file to paste the real input into.
+//!
+//! The registries are edited as text, so they're expected to look the way
+//! `rustfmt` leaves them: a `pub mod` line per module, and the lists of days
+//! and years in `y{year}/mod.rs` and `solver.rs`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+/// How long a line can get before `rustfmt` splits a list over several.
+const MAX_WIDTH: usize = 100;
+
+/// How long the list itself can get, likewise.
+const MAX_ARRAY_WIDTH: usize = 60;
+
+/// Adds the module for a day to the crate at `root`, and an empty input for
+/// it in `inputs_dir` unless there's one already. Returns the files that were
+/// created or changed.
+pub fn new_day(root: &Path, inputs_dir: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    if !src.join("lib.rs").is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no {}; run this from the crate's root",
+                src.join("lib.rs").display()
+            ),
+        ));
+    }
+
+    let year_dir = src.join(format!("y{}", year));
+    let module = year_dir.join(format!("day{:02}.rs", day));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    let mut changed = Vec::new();
+    let year_mod = year_dir.join("mod.rs");
+    if year_mod.exists() {
+        edit(&year_mod, |text| {
+            let text = add_module(text, &format!("day{:02}", day));
+            let entry = format!("&day{:02}::Day{}", day, day);
+            add_to_list(&text, "    days: &[", &entry)
+        })?;
+    } else {
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&year_mod, year_template(year, day))?;
+
+        edit(&src.join("lib.rs"), |text| {
+            Some(add_module(text, &format!("y{}", year)))
+        })?;
+        changed.push(src.join("lib.rs"));
+
+        let entry = format!("&y{}::YEAR", year);
+        edit(&src.join("solver.rs"), |text| {
+            add_to_list(text, "pub static YEARS: &[&Year] = &[", &entry)
+        })?;
+        changed.push(src.join("solver.rs"));
+    }
+    changed.push(year_mod);
+
+    fs::write(&module, day_template(day))?;
+    changed.push(module);
+
+    let input = input::default_path(inputs_dir, year, day);
+    if !input.exists() {
+        if let Some(dir) = input.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&input, "")?;
+        changed.push(input);
+    }
+
+    Ok(changed)
+}
+
+/// Rewrites a file, or fails if `change` can't find its place in it.
+fn edit(path: &Path, change: impl FnOnce(&str) -> Option<String>) -> io::Result<()> {
+    let text = fs::read_to_string(path)?;
+    match change(&text) {
+        Some(text) => fs::write(path, text),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "couldn't find where to register the day in {}",
+                path.display()
+            ),
+        )),
+    }
+}
+
+/// Adds `pub mod {name};` among the other `pub mod` lines, in order.
+fn add_module(text: &str, name: &str) -> String {
+    let line = format!("pub mod {};", name);
+    let mut lines: Vec<&str> = text.lines().collect();
+    let last_module = lines.iter().rposition(|l| l.starts_with("pub mod "));
+    let position = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod ") && *l > line.as_str())
+        .or_else(|| last_module.map(|idx| idx + 1))
+        .unwrap_or(lines.len());
+    lines.insert(position, &line);
+
+    lines.join("\n") + "\n"
+}
+
+/// Adds `entry` to the list that starts with `opening` (everything up to the
+/// `[`), in order, and lays the list out on one line or one entry per line,
+/// the way `rustfmt` would. `None` if there's no such list.
+fn add_to_list(text: &str, opening: &str, entry: &str) -> Option<String> {
+    let start = text.find(opening)? + opening.len();
+    let end = start + text[start..].find(']')?;
+
+    let mut entries: Vec<&str> = text[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    entries.push(entry);
+    entries.sort_unstable();
+    entries.dedup();
+
+    let indent = &opening[..opening.len() - opening.trim_start().len()];
+    let line_start = text[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let one_line = entries.join(", ");
+    // the rest of the line after the list, e.g. `];` or `],`
+    let closing = text[end..].lines().next().unwrap_or("");
+    let fits = start - line_start + one_line.len() + closing.len() <= MAX_WIDTH;
+    let list = if fits && one_line.len() + 2 <= MAX_ARRAY_WIDTH {
+        one_line
+    } else {
+        let lines: String = entries
+            .iter()
+            .map(|entry| format!("{}    {},\n", indent, entry))
+            .collect();
+        format!("\n{}{}", lines, indent)
+    };
+
+    Some(format!("{}{}{}", &text[..start], list, &text[end..]))
+}
+
+fn year_template(year: u32, day: u32) -> String {
+    format!(
+        "//! The puzzles of Advent of Code {year}.
+
+use crate::solver::Year;
+
+pub mod day{day:02};
+
+/// The days of the year, in order.
+pub static YEAR: Year = Year {{
+    year: {year},
+    days: &[&day{day:02}::Day{day}],
+}};
+",
+        year = year,
+        day = day
+    )
+}
+
+fn day_template(day: u32) -> String {
+    format!(
+        "use crate::error::Error;
+use crate::params::Params;
+use crate::parse;
+use crate::solver::{{Answer, Example, Solver}};
+
+pub struct Day{day};
+
+impl Solver for Day{day} {{
+    const DAY: u32 = {day};
+    const TITLE: &'static str = \"TODO\";
+    const EXAMPLES: &'static [Example] = &[Example {{
+        input: EXAMPLE,
+        part_one: None,
+        part_two: None,
+        params: &[],
+    }}];
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {{
+        Ok(parse::lines(input)
+            .map(|line| line.text.trim().to_string())
+            .collect())
+    }}
+
+    fn part_one(_lines: &Vec<String>, _params: &Params) -> Result<Answer, Error> {{
+        Err(Error::NoSolution(\"not solved yet\".to_string()))
+    }}
+
+    fn part_two(_lines: &Vec<String>, _params: &Params) -> Result<Answer, Error> {{
+        Err(Error::NoSolution(\"not solved yet\".to_string()))
+    }}
+}}
+
+const EXAMPLE: &str = \"\\
+\";
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use crate::solver::{{check_examples, Part}};
+
+    #[test]
+    fn examples() {{
+        check_examples::<Day{day}>(Part::BOTH);
+    }}
+}}
+",
+        day = day
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists() {
+        let text = "pub static YEARS: &[&Year] = &[&y2020::YEAR];\n";
+        assert_eq!(
+            add_to_list(text, "pub static YEARS: &[&Year] = &[", "&y2021::YEAR").unwrap(),
+            "pub static YEARS: &[&Year] = &[&y2020::YEAR, &y2021::YEAR];\n"
+        );
+
+        // short lists go on one line, and long ones a line per entry
+        let text = "    days: &[&day01::Day1, &day03::Day3],\n";
+        let text = add_to_list(text, "    days: &[", "&day02::Day2").unwrap();
+        assert_eq!(
+            text,
+            "    days: &[&day01::Day1, &day02::Day2, &day03::Day3],\n"
+        );
+        let text = add_to_list(&text, "    days: &[", "&day04::Day4").unwrap();
+        let text = add_to_list(&text, "    days: &[", "&day05::Day5").unwrap();
+        let entries: String = (1..=5)
+            .map(|n| format!("        &day0{}::Day{},\n", n, n))
+            .collect();
+        assert_eq!(text, format!("    days: &[\n{}    ],\n", entries));
+        assert_eq!(add_to_list(&text, "    years: &[", "&y2021::YEAR"), None);
+
+        let text = "pub mod grid;\npub mod y2020;\n\npub use grid::Grid;\n";
+        assert_eq!(
+            add_module(text, "y2021"),
+            "pub mod grid;\npub mod y2020;\npub mod y2021;\n\npub use grid::Grid;\n"
+        );
+        assert_eq!(
+            add_module(text, "answers"),
+            "pub mod answers;\n".to_string() + text
+        );
+    }
+
+    #[test]
+    fn adds_days_and_years() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let src = root.join("src");
+        fs::create_dir_all(src.join("y2020")).unwrap();
+        fs::write(src.join("lib.rs"), include_str!("lib.rs")).unwrap();
+        fs::write(src.join("solver.rs"), include_str!("solver.rs")).unwrap();
+        fs::write(src.join("y2020/mod.rs"), include_str!("y2020/mod.rs")).unwrap();
+        let inputs = root.join("inputs");
+
+        let changed = new_day(&root, &inputs, 2021, 3).unwrap();
+        assert_eq!(changed.len(), 5);
+        new_day(&root, &inputs, 2021, 1).unwrap();
+        let error = new_day(&root, &inputs, 2021, 3).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+
+        let read = |path: &str| fs::read_to_string(src.join(path)).unwrap();
+        assert!(read("lib.rs").contains("pub mod y2020;\npub mod y2021;\n"));
+        assert!(read("solver.rs").contains("= &[&y2020::YEAR, &y2021::YEAR];"));
+        let year = read("y2021/mod.rs");
+        assert!(year.contains("pub mod day01;\npub mod day03;\n"));
+        assert!(year.contains("days: &[&day01::Day1, &day03::Day3],"));
+        assert!(read("y2021/day03.rs").contains("impl Solver for Day3 {"));
+        assert!(inputs.join("2021/day01.txt").is_file());
+
+        // a year that's already there only gets the day
+        let changed = new_day(&root, &inputs, 2020, 26).unwrap();
+        assert_eq!(changed.len(), 3);
+        assert!(
+            read("y2020/mod.rs").contains("        &day25::Day25,\n        &day26::Day26,\n    ],")
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,564 @@
+//! A small HTTP/1.1 server for solving puzzles without going through the
+//! command line:
+//!
+//! - `GET /days` lists the days, their parts and their parameters.
+//! - `POST /day/{n}/part/{p}` solves a part, with the input as the body.
+//!   Parameters go in the query string, e.g. `/day/15/part/2?turns=1000`.
+//!
+//! Those are the latest year's days; a year in front, like `/2020/days`,
+//! picks another.
+//!
+//! Answers and errors are JSON, like `--format json`. Each connection gets a
+//! single response before it's closed.
+//!
+//! Connections are handled, and parts solved, on two pools of threads, with
+//! a few requests allowed to wait for a thread; any more get a 503. A part
+//! that isn't solved in time is abandoned: days 15 and 23 stop at their next
+//! check, but the others can't be stopped, and hold on to their thread until
+//! they finish.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::cancel::Cancel;
+use crate::error::Error;
+use crate::json::Json;
+use crate::params::Params;
+use crate::pool::Pool;
+use crate::solver::{self, Part, Solution, Year};
+
+/// The most a request body can be. The biggest puzzle inputs are about 20KB.
+const MAX_BODY: usize = 1 << 20;
+
+/// The most the request line and headers can be.
+const MAX_HEADER: usize = 16 << 10;
+
+/// How long to wait for the client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long solving can take when no timeout is given.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How many requests can wait for a thread, for each thread.
+const QUEUE_PER_THREAD: usize = 4;
+
+/// How many connections can be handled at once, for each solver thread.
+/// Most of them are only waiting, for the request or for the answer.
+const CONNECTIONS_PER_THREAD: usize = 4;
+
+pub struct Server {
+    listener: TcpListener,
+    /// How long to wait for an answer before giving up on a request.
+    timeout: Duration,
+    /// Threads reading requests and writing responses.
+    connections: Pool,
+    /// Threads solving, so that a request can stop waiting for its answer.
+    solvers: Arc<Pool>,
+}
+
+/// A response, before it's written out.
+struct Response {
+    status: u16,
+    body: Json,
+}
+
+impl Response {
+    fn ok(body: Json) -> Response {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Response {
+        let error = Json::object(vec![
+            ("kind", "http".into()),
+            ("message", message.into().into()),
+        ]);
+        Response {
+            status,
+            body: Json::object(vec![("error", error)]),
+        }
+    }
+}
+
+impl Server {
+    /// Starts listening on the address, e.g. `127.0.0.1:2020`, solving on
+    /// `threads` threads. Port 0 picks a free port, which `local_addr` tells.
+    pub fn bind(addr: impl ToSocketAddrs, timeout: Duration, threads: usize) -> io::Result<Server> {
+        let threads = threads.max(1);
+        let n_connections = threads * CONNECTIONS_PER_THREAD;
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            timeout,
+            connections: Pool::new(n_connections, n_connections * QUEUE_PER_THREAD),
+            solvers: Arc::new(Pool::new(threads, threads * QUEUE_PER_THREAD)),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Handles connections until the listener fails.
+    pub fn run(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let timeout = self.timeout;
+            let solvers = Arc::clone(&self.solvers);
+            let mut busy = stream.try_clone();
+            let queued = self.connections.execute(move || {
+                // the client hanging up early isn't our problem
+                let _ = handle(stream, timeout, &solvers);
+            });
+            if !queued {
+                if let Ok(stream) = &mut busy {
+                    let _ = write_response(stream, &too_busy());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A request, as far as the server cares.
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+fn handle(mut stream: TcpStream, timeout: Duration, solvers: &Pool) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let response = match read_request(&mut stream) {
+        Ok(request) => respond(request, timeout, solvers),
+        Err(response) => response,
+    };
+
+    write_response(&mut stream, &response)
+}
+
+/// The response when there are already as many requests as can wait.
+fn too_busy() -> Response {
+    Response::error(503, "too many requests at once; try again later")
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let body = format!("{}\n", response.body);
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+/// Reads the request, or works out the error response if it's no good.
+fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let io_error = |error: io::Error| match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Response::error(408, "timed out reading the request")
+        }
+        _ => Response::error(400, format!("couldn't read the request: {}", error)),
+    };
+
+    let mut reader = BufReader::new(stream).take(MAX_HEADER as u64);
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).map_err(io_error)? == 0 {
+            return Err(Response::error(400, "the request ended early"));
+        }
+        if !line.ends_with('\n') {
+            return Err(Response::error(400, "the request headers are too long"));
+        }
+        let line = line.trim_end().to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    let request_line = lines.first().map(String::as_str).unwrap_or("");
+    let (method, target) = match request_line.split(' ').collect::<Vec<_>>()[..] {
+        [method, target, version] if version.starts_with("HTTP/1.") => (method, target),
+        _ => return Err(Response::error(400, "malformed request line")),
+    };
+
+    let mut content_length = 0;
+    for header in &lines[1..] {
+        let (name, value) = match header.split_once(':') {
+            Some((name, value)) => (name.trim().to_ascii_lowercase(), value.trim()),
+            None => return Err(Response::error(400, "malformed header")),
+        };
+        match name.as_str() {
+            "content-length" => {
+                content_length = value
+                    .parse::<usize>()
+                    .map_err(|_| Response::error(400, "malformed Content-Length"))?
+            }
+            "transfer-encoding" => {
+                return Err(Response::error(501, "transfer encodings aren't supported"))
+            }
+            _ => (),
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(Response::error(
+            413,
+            format!("the body can be at most {} bytes", MAX_BODY),
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    let mut reader = reader.into_inner();
+    reader.read_exact(&mut body).map_err(io_error)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+/// Decodes `%XX` escapes and `+` for spaces, as in a query string.
+fn percent_decode(text: &str) -> Result<String, Response> {
+    let bad = || Response::error(400, format!("malformed query string `{}`", text));
+
+    let mut bytes = Vec::new();
+    let mut rest = text.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        match byte {
+            b'%' if after.len() >= 2 => {
+                let hex = std::str::from_utf8(&after[..2]).map_err(|_| bad())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| bad())?);
+                rest = &after[2..];
+            }
+            b'%' => return Err(bad()),
+            b'+' => {
+                bytes.push(b' ');
+                rest = after;
+            }
+            _ => {
+                bytes.push(byte);
+                rest = after;
+            }
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| bad())
+}
+
+fn respond(request: Request, timeout: Duration, solvers: &Pool) -> Response {
+    let mut segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let year = match segments[0].parse() {
+        Ok(year) => match solver::year(year) {
+            Some(year) => {
+                segments.remove(0);
+                year
+            }
+            None => return Response::error(404, format!("no solutions for {}", year)),
+        },
+        Err(_) => solver::latest(),
+    };
+
+    match (request.method.as_str(), &segments[..]) {
+        ("GET", ["days"]) => Response::ok(days(year)),
+        (_, ["days"]) => Response::error(405, "use GET for /days"),
+        ("POST", ["day", day, "part", part]) => solve(year, day, part, &request, timeout, solvers),
+        (_, ["day", _, "part", _]) => Response::error(405, "use POST to solve a part"),
+        _ => Response::error(404, format!("no such path `{}`", request.path)),
+    }
+}
+
+/// Every day of the year, with its parts and parameters.
+fn days(year: &Year) -> Json {
+    let days = year.days.iter().map(|solution| {
+        let parts: Vec<u32> = solution.parts().iter().map(|part| part.number()).collect();
+        let params: Vec<Json> = solution
+            .params()
+            .iter()
+            .map(|param| {
+                Json::object(vec![
+                    ("name", param.name.into()),
+                    ("part", param.part.map(Part::number).into()),
+                    ("default", param.default.into()),
+                    ("help", param.help.into()),
+                ])
+            })
+            .collect();
+        Json::object(vec![
+            ("day", solution.day().into()),
+            ("title", solution.title().into()),
+            ("parts", parts.into()),
+            ("params", params.into()),
+        ])
+    });
+
+    Json::object(vec![
+        ("year", year.year.into()),
+        ("days", days.collect::<Vec<_>>().into()),
+    ])
+}
+
+fn solve(
+    year: &Year,
+    day: &str,
+    part: &str,
+    request: &Request,
+    timeout: Duration,
+    solvers: &Pool,
+) -> Response {
+    let solution = match day.parse().ok().and_then(|day| year.find(day)) {
+        Some(solution) => solution,
+        None => return Response::error(404, format!("no solution for day {}", day)),
+    };
+    let part = match part.parse().ok().and_then(Part::from_number) {
+        Some(part) if solution.parts().contains(&part) => part,
+        _ => return Response::error(404, format!("day {} has no part {}", day, part)),
+    };
+    let input = match String::from_utf8(request.body.clone()) {
+        Ok(input) => input,
+        Err(_) => return Response::error(400, "the input isn't UTF-8"),
+    };
+
+    let mut params = Params::new();
+    for (key, value) in &request.query {
+        params.set(key, value);
+    }
+    if let Err(error) = params.check(solution.day(), solution.params()) {
+        return error_response(solution, part, &error);
+    }
+
+    // solve on another thread, so that we can stop waiting for it, and ask
+    // the solver to stop too if it's taking too long
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    let cancel = Cancel::new();
+    let solver_cancel = cancel.clone();
+    let queued = solvers.execute(move || {
+        // the request may have given up while this was waiting for a thread
+        if solver_cancel.is_cancelled() {
+            return;
+        }
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            solver_cancel.run(|| solution.run_with_details(&input, part, &params))
+        }));
+        let _ = sender.send(result);
+    });
+    if !queued {
+        return too_busy();
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok((answer, details)))) => {
+            let mut fields = fields(solution, part);
+            fields.push(("answer", (&answer).into()));
+            if let Some(details) = details {
+                fields.push(("details", details));
+            }
+            fields.push((
+                "time_ms",
+                (start.elapsed().as_micros() as f64 / 1000.0).into(),
+            ));
+            Response::ok(Json::object(fields))
+        }
+        Ok(Ok(Err(error))) => error_response(solution, part, &error),
+        Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Response::error(500, "the solver panicked")
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Response::error(
+                504,
+                format!("no answer after {:.1}s", timeout.as_secs_f64()),
+            )
+        }
+    }
+}
+
+/// The fields saying which part a response is about.
+fn fields(solution: &dyn Solution, part: Part) -> Vec<(&'static str, Json)> {
+    vec![
+        ("day", solution.day().into()),
+        ("part", part.number().into()),
+    ]
+}
+
+fn error_response(solution: &dyn Solution, part: Part, error: &Error) -> Response {
+    let status = match error {
+        Error::InvalidParam(_) => 400,
+        Error::NoSuchPart(..) => 404,
+        Error::Parse(_) | Error::NoSolution(_) => 422,
+        // only once the request has timed out
+        Error::Cancelled => 504,
+    };
+    let mut fields = fields(solution, part);
+    fields.push(("error", error.into()));
+    Response {
+        status,
+        body: Json::object(fields),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// Starts a server on a free port, returning its address.
+    fn start(timeout: Duration) -> SocketAddr {
+        start_with_threads(timeout, 2)
+    }
+
+    fn start_with_threads(timeout: Duration, threads: usize) -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0", timeout, threads).unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    /// Sends a raw request, returning the status and body of the response.
+    fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    fn post(addr: SocketAddr, path: &str, body: &str) -> (u16, String) {
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        );
+        send(addr, &request)
+    }
+
+    #[test]
+    fn solves_parts() {
+        let addr = start(DEFAULT_TIMEOUT);
+
+        let (status, body) = post(addr, "/day/1/part/1", "1721\n979\n366\n299\n675\n1456\n");
+        assert_eq!(status, 200);
+        assert!(
+            body.starts_with(r#"{"day":1,"part":1,"answer":514579,"#),
+            "{}",
+            body
+        );
+
+        let (status, body) = post(addr, "/day/15/part/2?turns=2020", "0,3,6\n");
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""answer":436"#), "{}", body);
+    }
+
+    #[test]
+    fn lists_days() {
+        let addr = start(DEFAULT_TIMEOUT);
+        let (status, body) = send(addr, "GET /days HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 200);
+        assert!(body.contains(r#"{"day":25,"title":"Combo Breaker","parts":[1],"params":[]}"#));
+
+        let (status, body) = send(addr, "GET /2020/days HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 200);
+        assert!(
+            body.starts_with(r#"{"year":2020,"days":[{"day":1,"#),
+            "{}",
+            body
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let addr = start(DEFAULT_TIMEOUT);
+        let cases = [
+            (post(addr, "/day/26/part/1", ""), 404),
+            (post(addr, "/1999/day/1/part/1", ""), 404),
+            (post(addr, "/day/25/part/2", ""), 404),
+            (post(addr, "/day/1/part/1?nope=1", "1\n"), 400),
+            (post(addr, "/day/1/part/1", "1\nx\n"), 422),
+            (send(addr, "GET /day/1/part/1 HTTP/1.1\r\n\r\n"), 405),
+            (send(addr, "GET /nowhere HTTP/1.1\r\n\r\n"), 404),
+            (send(addr, "nonsense\r\n\r\n"), 400),
+        ];
+        for ((status, body), expected) in cases.iter() {
+            assert_eq!(status, expected, "{}", body);
+            assert!(body.contains(r#""error":"#), "{}", body);
+        }
+    }
+
+    #[test]
+    fn survives_bad_programs() {
+        let addr = start(DEFAULT_TIMEOUT);
+
+        // a rule that loops would overflow the stack, which nothing catches
+        let grammar = "0: 8 11\n8: 42\n11: 42 31\n42: 1 42\n31: 1\n1: \"a\"\n\naaa\n";
+        let (status, body) = post(addr, "/day/19/part/2", grammar);
+        assert_eq!(status, 422, "{}", body);
+        assert!(body.contains("refers back to itself"), "{}", body);
+
+        let (status, body) = post(addr, "/day/8/part/1", "jmp -5\nacc +1\n");
+        assert_eq!(status, 200, "{}", body);
+        assert!(body.contains(r#""answer":0"#), "{}", body);
+
+        // and the server is still there afterwards
+        let (status, body) = post(addr, "/day/8/part/2", "jmp -5\nacc +1\n");
+        assert_eq!(status, 200, "{}", body);
+    }
+
+    #[test]
+    fn times_out() {
+        // the game that times out has to stop, to free the only thread
+        let addr = start_with_threads(Duration::from_millis(200), 1);
+        let (status, _) = post(addr, "/day/15/part/2", "0,3,6\n");
+        assert_eq!(status, 504);
+        let (status, body) = post(addr, "/day/15/part/1", "0,3,6\n");
+        assert_eq!(status, 200, "{}", body);
+    }
+
+    #[test]
+    fn query_strings() {
+        assert_eq!(percent_decode("a%3Db+c").ok(), Some("a=b c".to_string()));
+        assert!(percent_decode("%zz").is_err());
+    }
+}
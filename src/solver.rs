@@ -0,0 +1,439 @@
+use std::fmt;
+use std::time::Instant;
+
+use crate::bench::Timing;
+use crate::error::Error;
+use crate::json::Json;
+use crate::params::{Param, Params};
+use crate::render::Frame;
+use crate::rng::Rng;
+use crate::*;
+
+/// One of the two halves of a day's puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: &'static [Part] = &[Part::One, Part::Two];
+
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.number(), f)
+    }
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A worked example from a puzzle statement, with the expected answers.
+///
+/// Some examples only apply to one of the parts, so the other is `None`.
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+    /// Parameters the example needs that aren't the defaults, e.g. a shorter
+    /// preamble than the real input's.
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    /// The example's parameters, with `overrides` on top.
+    pub fn params(&self, overrides: &Params) -> Params {
+        let mut params = Params::new();
+        for (name, value) in self.params.iter().copied().chain(overrides.iter()) {
+            params.set(name, value);
+        }
+        params
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part_one,
+            Part::Two => self.part_two,
+        }
+    }
+}
+
+/// A solution to a single day.
+///
+/// The input is parsed once into `Input`, which is then shared by both parts.
+pub trait Solver {
+    const DAY: u32;
+    const TITLE: &'static str;
+    /// Parts that have a puzzle (day 25 only has one).
+    const PARTS: &'static [Part] = Part::BOTH;
+    const EXAMPLES: &'static [Example] = &[];
+    /// Constants of the puzzle that can be changed, e.g. to try a smaller case.
+    const PARAMS: &'static [Param] = &[];
+    /// The size `generate` uses when none is given, for inputs about as big
+    /// as a real one.
+    const INPUT_SIZE: usize = 100;
+    /// Goes up when a change could give different answers, so that answers
+    /// cached by earlier versions aren't used.
+    const VERSION: u32 = 1;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    /// Every problem with the input, for checking it without solving: all
+    /// of what `parse` would reject rather than just the first, and anything
+    /// the parts assume that `parse` lets through. By default, only the
+    /// error from `parse`.
+    fn check(input: &str) -> Vec<Error> {
+        Self::parse(input).err().into_iter().collect()
+    }
+
+    /// Anything unusual about the input that isn't a problem for the
+    /// solver, like a size that real inputs never have. By default, nothing.
+    fn warnings(_input: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Solves part one. `params` has a value for each of the part's `PARAMS`.
+    fn part_one(input: &Self::Input, params: &Params) -> Result<Answer, Error>;
+
+    fn part_two(_input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::NoSuchPart(Self::DAY, Part::Two))
+    }
+
+    /// Structured information about how the part was solved, for the JSON
+    /// output. Most days don't have any.
+    fn details(_input: &Self::Input, _part: Part, _params: &Params) -> Option<Json> {
+        None
+    }
+
+    /// Makes up a random input that can be solved with the default
+    /// parameters. What `size` counts (lines, tiles, the side of a grid...)
+    /// is up to the day. Days without a generator return `None`.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Like `generate`, but for an input that can be solved with `params`
+    /// (resolved for the first part). Only days whose inputs depend on their
+    /// parameters need this; the rest use `generate` whatever they are.
+    fn generate_with(
+        rng: &mut Rng,
+        size: usize,
+        _params: &Params,
+    ) -> Result<Option<String>, Error> {
+        Ok(Self::generate(rng, size))
+    }
+
+    /// Pictures of how the part plays out, a frame per step, for the days
+    /// that are played out on a map. Most days don't have any.
+    fn frames(_input: &Self::Input, _part: Part, _params: &Params) -> Result<Vec<Frame>, Error> {
+        Ok(Vec::new())
+    }
+
+    /// Parses the input and solves the given part, with the default parameters.
+    fn solve(input: &str, part: Part) -> Result<Answer, Error> {
+        Self::solve_with(input, part, &Params::new())
+    }
+
+    /// Like `solve`, but with some of the parameters changed.
+    fn solve_with(input: &str, part: Part, overrides: &Params) -> Result<Answer, Error> {
+        let params = overrides.resolve(Self::DAY, Self::PARAMS, part)?;
+        let parsed = Self::parse(input).map_err(|error| error.in_day(Self::DAY))?;
+        Self::solve_parsed(&parsed, part, &params)
+    }
+
+    /// Like `solve_with`, but also returns the details of the part.
+    fn solve_with_details(
+        input: &str,
+        part: Part,
+        overrides: &Params,
+    ) -> Result<(Answer, Option<Json>), Error> {
+        let params = overrides.resolve(Self::DAY, Self::PARAMS, part)?;
+        let parsed = Self::parse(input).map_err(|error| error.in_day(Self::DAY))?;
+        let answer = Self::solve_parsed(&parsed, part, &params)?;
+        Ok((answer, Self::details(&parsed, part, &params)))
+    }
+
+    /// Like `solve_with`, but returns how long parsing and solving took
+    /// (and how much heap they used, if that's counted) instead of the
+    /// answer.
+    fn time(input: &str, part: Part, overrides: &Params) -> Result<Timing, Error> {
+        let params = overrides.resolve(Self::DAY, Self::PARAMS, part)?;
+
+        let (times, heap) = heap::measure(|| -> Result<_, Error> {
+            let start = Instant::now();
+            let parsed = Self::parse(input).map_err(|error| error.in_day(Self::DAY))?;
+            let parse = start.elapsed();
+
+            let start = Instant::now();
+            Self::solve_parsed(&parsed, part, &params)?;
+            Ok((parse, start.elapsed()))
+        });
+        let (parse, solve) = times?;
+
+        Ok(Timing { parse, solve, heap })
+    }
+
+    /// Solves the given part of an input that has already been parsed.
+    fn solve_parsed(parsed: &Self::Input, part: Part, params: &Params) -> Result<Answer, Error> {
+        match part {
+            Part::One => Self::part_one(parsed, params),
+            Part::Two => Self::part_two(parsed, params),
+        }
+    }
+}
+
+/// Object-safe view of a `Solver`, so that every day can be kept in one registry.
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn parts(&self) -> &'static [Part];
+    fn examples(&self) -> &'static [Example];
+    fn params(&self) -> &'static [Param];
+    fn input_size(&self) -> usize;
+    fn version(&self) -> u32;
+    /// Generates an input for the parameters with a fresh random number
+    /// generator, so that the same seed always gives the same input.
+    fn generate(&self, size: usize, seed: u64, params: &Params) -> Result<Option<String>, Error>;
+    /// Every problem with the input, without solving it.
+    fn check(&self, input: &str) -> Vec<Error>;
+    /// Anything unusual about the input that isn't a problem.
+    fn warnings(&self, input: &str) -> Vec<String>;
+    fn run(&self, input: &str, part: Part, params: &Params) -> Result<Answer, Error>;
+    fn run_with_details(
+        &self,
+        input: &str,
+        part: Part,
+        params: &Params,
+    ) -> Result<(Answer, Option<Json>), Error>;
+    fn time(&self, input: &str, part: Part, params: &Params) -> Result<Timing, Error>;
+    /// Parses the input and draws the frames of the part, if it has any.
+    fn render(&self, input: &str, part: Part, params: &Params) -> Result<Vec<Frame>, Error>;
+}
+
+impl<S: Solver + Sync> Solution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn input_size(&self) -> usize {
+        S::INPUT_SIZE
+    }
+
+    fn version(&self) -> u32 {
+        S::VERSION
+    }
+
+    fn generate(
+        &self,
+        size: usize,
+        seed: u64,
+        overrides: &Params,
+    ) -> Result<Option<String>, Error> {
+        let params = overrides.resolve(S::DAY, S::PARAMS, S::PARTS[0])?;
+        S::generate_with(&mut Rng::new(seed), size, &params)
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        S::check(input)
+            .into_iter()
+            .map(|error| error.in_day(S::DAY))
+            .collect()
+    }
+
+    fn warnings(&self, input: &str) -> Vec<String> {
+        S::warnings(input)
+    }
+
+    fn run(&self, input: &str, part: Part, params: &Params) -> Result<Answer, Error> {
+        S::solve_with(input, part, params)
+    }
+
+    fn run_with_details(
+        &self,
+        input: &str,
+        part: Part,
+        params: &Params,
+    ) -> Result<(Answer, Option<Json>), Error> {
+        S::solve_with_details(input, part, params)
+    }
+
+    fn time(&self, input: &str, part: Part, params: &Params) -> Result<Timing, Error> {
+        S::time(input, part, params)
+    }
+
+    fn render(&self, input: &str, part: Part, overrides: &Params) -> Result<Vec<Frame>, Error> {
+        let params = overrides.resolve(S::DAY, S::PARAMS, part)?;
+        let parsed = S::parse(input).map_err(|error| error.in_day(S::DAY))?;
+        S::frames(&parsed, part, &params)
+    }
+}
+
+/// The days of one year.
+pub struct Year {
+    pub year: u32,
+    pub days: &'static [&'static dyn Solution],
+}
+
+impl Year {
+    /// Looks up the solver for the given day.
+    pub fn find(&self, day: u32) -> Option<&'static dyn Solution> {
+        self.days.iter().find(|s| s.day() == day).copied()
+    }
+}
+
+/// All years, in order.
+pub static YEARS: &[&Year] = &[&y2020::YEAR];
+
+/// Looks up the days of the given year.
+pub fn year(year: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year).copied()
+}
+
+/// The most recent year, which is the one used unless another is asked for.
+pub fn latest() -> &'static Year {
+    YEARS.last().unwrap()
+}
+
+/// Checks a day's examples for the given parts, panicking on a wrong answer.
+#[cfg(test)]
+pub fn check_examples<S: Solver>(parts: &[Part]) {
+    for (idx, example) in S::EXAMPLES.iter().enumerate() {
+        for part in parts {
+            if let Some(expected) = example.expected(*part) {
+                let params = example.params(&Params::new());
+                let answer = S::solve_with(example.input, *part, &params).unwrap();
+                assert_eq!(
+                    answer.to_string(),
+                    expected,
+                    "day {} part {}, example {}",
+                    S::DAY,
+                    part,
+                    idx + 1
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_inputs_solve() {
+        // keep the slow days quick
+        let quick = [("turns", "3000"), ("moves", "100"), ("cups", "100")];
+
+        // every 2020 day has a generator
+        for solution in y2020::YEAR.days {
+            let mut overrides = Params::new();
+            for (name, value) in quick.iter() {
+                if solution.params().iter().any(|param| param.name == *name) {
+                    overrides.set(name, value);
+                }
+            }
+
+            let size = solution.input_size().min(10);
+            for seed in 0..3 {
+                let input = solution
+                    .generate(size, seed, &Params::new())
+                    .unwrap()
+                    .unwrap();
+                let problems = solution.check(&input);
+                assert!(
+                    problems.is_empty(),
+                    "day {}, seed {}: {:?}\n{}",
+                    solution.day(),
+                    seed,
+                    problems,
+                    input
+                );
+                for part in solution.parts() {
+                    let answer = solution.run(&input, *part, &overrides);
+                    assert!(
+                        answer.is_ok(),
+                        "day {} part {}, seed {}: {:?}\n{}",
+                        solution.day(),
+                        part,
+                        seed,
+                        answer,
+                        input
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Step-by-step traces of what the solvers do, for finding where a wrong
+//! answer goes wrong.
+//!
+//! Solvers emit events with [`trace!`]: a kind, like `step` or `round`, and
+//! some named values. Events go nowhere until a sink is started (`--trace`),
+//! and then each one it wants is written on its own line, after the module
+//! it came from (so days before 10 have a leading zero):
+//!
+//! ```text
+//! day08 step pc=2 acc=1 instruction="jmp -2"
+//! ```
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::json::Json;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+struct Sink {
+    /// The kinds of event to write, or `None` for all of them.
+    kinds: Option<HashSet<String>>,
+    out: Box<dyn Write + Send>,
+}
+
+/// Starts writing events to `out`: every kind, or only the given ones.
+pub fn start(kinds: Option<Vec<String>>, out: Box<dyn Write + Send>) {
+    let kinds = kinds.map(|kinds| kinds.into_iter().collect());
+    *SINK.lock().unwrap() = Some(Sink { kinds, out });
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stops tracing, and flushes what was written.
+pub fn stop() -> io::Result<()> {
+    ENABLED.store(false, Ordering::Relaxed);
+    match SINK.lock().unwrap().take() {
+        Some(mut sink) => sink.out.flush(),
+        None => Ok(()),
+    }
+}
+
+/// Flushes what was written so far, unless the sink is busy (which it could
+/// be if the flush is from a panic while writing).
+pub fn flush() {
+    if let Ok(mut sink) = SINK.try_lock() {
+        if let Some(sink) = sink.as_mut() {
+            let _ = sink.out.flush();
+        }
+    }
+}
+
+/// Whether events of this kind are being written.
+pub fn enabled(kind: &str) -> bool {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    match SINK.lock().unwrap().as_ref() {
+        Some(sink) => sink.kinds.as_ref().is_none_or(|kinds| kinds.contains(kind)),
+        None => false,
+    }
+}
+
+/// Writes an event from the module at `path`. A sink that can't be written to
+/// any more is dropped.
+pub fn emit(path: &str, kind: &str, values: Vec<(&str, Json)>) {
+    let mut sink = SINK.lock().unwrap();
+    let out = match sink.as_mut() {
+        Some(sink) => &mut sink.out,
+        None => return,
+    };
+
+    let source = path.rsplit("::").next().unwrap_or(path);
+    let mut line = format!("{} {}", source, kind);
+    for (name, value) in values {
+        line.push_str(&format!(" {}={}", name, value));
+    }
+    if writeln!(out, "{}", line).is_err() {
+        ENABLED.store(false, Ordering::Relaxed);
+        *sink = None;
+    }
+}
+
+/// Emits a trace event of the given kind, with named values that convert to
+/// [`Json`]. The values aren't worked out unless the kind is being traced.
+///
+/// ```
+/// # use aoc_2020_rust::trace;
+/// let (pc, acc) = (3, 1);
+/// trace!("step", pc = pc, acc = acc, instruction = "jmp -3");
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($kind:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($kind) {
+            $crate::trace::emit(
+                module_path!(),
+                $kind,
+                vec![$((stringify!($name), $crate::json::Json::from($value))),*],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// A sink that can be read back.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn filters_by_kind() {
+        // other tests could be tracing at the same time, so only look at
+        // kinds no solver uses
+        let buffer = Buffer::default();
+        start(
+            Some(vec!["test-kept".to_string()]),
+            Box::new(buffer.clone()),
+        );
+        trace!("test-kept", n = 1, name = "a \"b\"", cards = vec![3, 4]);
+        trace!("test-dropped", n = 2);
+        stop().unwrap();
+        trace!("test-kept", n = 3);
+
+        let written = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            written,
+            "tests test-kept n=1 name=\"a \\\"b\\\"\" cards=[3,4]\n"
+        );
+    }
+}
@@ -0,0 +1,630 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::log;
+use crate::params::{Param, Params};
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day1;
+
+/// Entries of the report that add up to the total.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expenses {
+    /// From smallest to largest.
+    pub entries: Vec<i64>,
+    pub product: i128,
+}
+
+impl Solver for Day1 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Report Repair";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("514579"),
+        part_two: Some("241861950"),
+        params: &[],
+    }];
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "total",
+            part: None,
+            default: "2020",
+            help: "what the entries must sum to",
+        },
+        Param {
+            name: "entries",
+            part: Some(Part::One),
+            default: "2",
+            help: "how many entries to add up",
+        },
+        Param {
+            name: "entries",
+            part: Some(Part::Two),
+            default: "3",
+            help: "how many entries to add up",
+        },
+    ];
+
+    /// Entries in the expense report, up to about 1000.
+    const INPUT_SIZE: usize = 200;
+
+    /// Sets of four or more entries are found another way, which can find a
+    /// different one when there are several.
+    const VERSION: u32 = 3;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        read_day1(input)
+    }
+
+    /// Real reports never repeat an entry, so a repeat is more likely a
+    /// mistake in copying it than part of the answer.
+    fn check(input: &str) -> Vec<Error> {
+        let mut errors = Vec::new();
+        let mut seen = HashMap::new();
+        for line in parse::lines(input) {
+            match line.parse::<i32>(line.text) {
+                Ok(value) => match seen.get(&value) {
+                    Some(first) => errors.push(line.error(
+                        line.text.trim(),
+                        format!("{} is already on line {}", value, first),
+                    )),
+                    None => {
+                        seen.insert(value, line.number);
+                    }
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+
+        errors
+    }
+
+    fn part_one(values: &Vec<i64>, params: &Params) -> Result<Answer, Error> {
+        find_expenses(values, params).map(|expenses| expenses.product.into())
+    }
+
+    fn part_two(values: &Vec<i64>, params: &Params) -> Result<Answer, Error> {
+        find_expenses(values, params).map(|expenses| expenses.product.into())
+    }
+
+    fn details(values: &Vec<i64>, _part: Part, params: &Params) -> Option<Json> {
+        let expenses = find_expenses(values, params).ok()?;
+        let entries = expenses.entries.into_iter().map(Json::from).collect();
+        Some(Json::object(vec![
+            ("entries", Json::Array(entries)),
+            ("product", expenses.product.into()),
+        ]))
+    }
+
+    fn generate_with(rng: &mut Rng, size: usize, params: &Params) -> Result<Option<String>, Error> {
+        let total = match params.get("total")? {
+            total if (MIN_TOTAL..=MAX_TOTAL).contains(&total) => total,
+            total => {
+                return Err(Error::InvalidParam(format!(
+                    "can only make up reports for totals from {} to {}, not {}",
+                    MIN_TOTAL, MAX_TOTAL, total
+                )))
+            }
+        };
+        Ok(Some(generate_report(rng, size, total)))
+    }
+}
+
+/// Reads the entries, sorted.
+fn read_day1(input: &str) -> Result<Vec<i64>, Error> {
+    let mut values = Vec::new();
+    for line in parse::lines(input) {
+        values.push(line.parse::<i32>(line.text)? as i64);
+    }
+    values.sort_unstable();
+
+    // check if all numbers are unique
+    for pair in values.windows(2).filter(|pair| pair[0] == pair[1]) {
+        log!("already have {}!", pair[0]);
+    }
+
+    Ok(values)
+}
+
+/// Finds the entries for a part, with the part's parameters.
+fn find_expenses(values: &[i64], params: &Params) -> Result<Expenses, Error> {
+    let count: usize = params.get("entries")?;
+    if count == 0 {
+        return Err(Error::InvalidParam(
+            "need at least 1 entry to add up".to_string(),
+        ));
+    }
+
+    expenses(values, count, params.get("total")?)
+}
+
+/// Finds `count` entries of the (sorted) report that sum to `total`, and
+/// multiplies them together.
+pub fn expenses(values: &[i64], count: usize, total: i64) -> Result<Expenses, Error> {
+    let entries = match find_entries(values, count, total) {
+        Some(entries) => entries,
+        None => {
+            let what = match count {
+                1 => "entry".to_string(),
+                2 => "pair".to_string(),
+                3 => "triplet".to_string(),
+                n => format!("set of {} entries", n),
+            };
+            return Err(Error::NoSolution(format!("no {} sums to {}", what, total)));
+        }
+    };
+    log!("found {:?}", entries);
+
+    let product = entries
+        .iter()
+        .try_fold(1i128, |product, entry| product.checked_mul(*entry as i128));
+    match product {
+        Some(product) => Ok(Expenses { entries, product }),
+        None => Err(Error::NoSolution(format!(
+            "the product of {:?} is too big",
+            entries
+        ))),
+    }
+}
+
+/// Finds `count` entries of the (sorted) report, each from a different
+/// line, that sum to `total`.
+///
+/// A pair is found with a pointer at each end of the report, closing in. For
+/// a triplet, each entry is tried in turn, smallest first, with a pair from
+/// the entries after it. Being sorted gives bounds on what the rest could add
+/// up to, so an entry is skipped when even the biggest rest would be too
+/// small, and the search stops once even the smallest rest would be too big;
+/// an entry the same as the one just tried isn't tried again. Four or more
+/// entries are found by `meet_in_the_middle`. That's O(n) for pairs, O(n^2)
+/// at worst for triplets and O(n^⌈count/2⌉ log n) for the rest, but in a
+/// report like the puzzle's only a few entries are small enough to be in a
+/// sum, and the bounds rule out the rest at once.
+pub fn find_entries(values: &[i64], count: usize, total: i64) -> Option<Vec<i64>> {
+    let mut entries = Vec::with_capacity(count);
+    if search(values, count, total, &mut entries) {
+        Some(entries)
+    } else {
+        None
+    }
+}
+
+/// Looks for the rest of the entries in `values`, adding them to `entries`.
+fn search(values: &[i64], count: usize, total: i64, entries: &mut Vec<i64>) -> bool {
+    if values.len() < count {
+        return false;
+    }
+
+    match count {
+        0 => total == 0,
+        1 => {
+            let found = values.binary_search(&total).is_ok();
+            if found {
+                entries.push(total);
+            }
+            found
+        }
+        2 => {
+            let (mut low, mut high) = (0, values.len() - 1);
+            while low < high {
+                match (values[low] + values[high]).cmp(&total) {
+                    Ordering::Less => low += 1,
+                    Ordering::Greater => high -= 1,
+                    Ordering::Equal => {
+                        entries.extend([values[low], values[high]]);
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+        3 => {
+            let rest = count - 1;
+            let biggest_rest: i64 = values[values.len() - rest..].iter().sum();
+            for (idx, value) in values[..=values.len() - count].iter().enumerate() {
+                if idx > 0 && *value == values[idx - 1] {
+                    continue;
+                }
+                let smallest_rest: i64 = values[idx + 1..=idx + rest].iter().sum();
+                if value + smallest_rest > total {
+                    break;
+                }
+                if value + biggest_rest < total {
+                    continue;
+                }
+
+                entries.push(*value);
+                if search(&values[idx + 1..], rest, total - value, entries) {
+                    return true;
+                }
+                entries.pop();
+            }
+            false
+        }
+        _ => match meet_in_the_middle(values, count, total) {
+            Some(found) => {
+                entries.extend(found);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// Finds four or more entries that sum to `total`, by meeting in the middle.
+///
+/// Going by position, a set is a first half of ⌈count/2⌉ entries followed by
+/// a second half of the rest. Every possible first half is listed in order
+/// of its sum, and so is every second half, and a pointer at each end closes
+/// in on the sums that add up to `total`. Halves with the same sum are as
+/// good as each other, except that the first half has to end before the
+/// second starts, so only the first half that ends earliest and the second
+/// half that starts latest are compared. A half is only listed if the rest of
+/// a set could make up the total, going by the same bounds as `search`.
+fn meet_in_the_middle(values: &[i64], count: usize, total: i64) -> Option<Vec<i64>> {
+    let n = values.len();
+    let mut prefix = vec![0; n + 1];
+    for (idx, value) in values.iter().enumerate() {
+        prefix[idx + 1] = prefix[idx] + value;
+    }
+    let sum = |range: Range<usize>| prefix[range.end] - prefix[range.start];
+    // the least and most that `needed` entries after `last` add up to
+    let after = |last: usize, needed: usize| {
+        if last + needed < n {
+            Some((sum(last + 1..last + 1 + needed), sum(n - needed..n)))
+        } else {
+            None
+        }
+    };
+
+    let (first_size, second_size) = (count.div_ceil(2), count / 2);
+    let firsts = Halves::list(values, first_size, 0, total, &|_, last, picked| {
+        after(last, first_size - picked + second_size)
+    });
+    // a second half leaves room for a first half before it
+    let seconds = Halves::list(
+        values,
+        second_size,
+        first_size,
+        total,
+        &|first, last, picked| {
+            let (least, most) = after(last, second_size - picked)?;
+            Some((
+                least + sum(0..first_size),
+                most + sum(first - first_size..first),
+            ))
+        },
+    );
+
+    let (mut low, mut high) = (0, seconds.sums.len());
+    while low < firsts.sums.len() && high > 0 {
+        let (first_sum, second_sum) = (firsts.sums[low].0, seconds.sums[high - 1].0);
+        match (first_sum + second_sum).cmp(&total) {
+            Ordering::Less => low += 1,
+            Ordering::Greater => high -= 1,
+            Ordering::Equal => {
+                let same_first = firsts.sums[low..].iter();
+                let low_end = low + same_first.take_while(|(sum, _)| *sum == first_sum).count();
+                let same_second = seconds.sums[..high].iter().rev();
+                let high_start = high
+                    - same_second
+                        .take_while(|(sum, _)| *sum == second_sum)
+                        .count();
+
+                let first = firsts.sums[low..low_end]
+                    .iter()
+                    .map(|(_, id)| firsts.pick(*id))
+                    .min_by_key(|pick| pick[pick.len() - 1])
+                    .unwrap();
+                let second = seconds.sums[high_start..high]
+                    .iter()
+                    .map(|(_, id)| seconds.pick(*id))
+                    .max_by_key(|pick| pick[0])
+                    .unwrap();
+                if first[first.len() - 1] < second[0] {
+                    return Some(first.iter().chain(second).map(|idx| values[*idx]).collect());
+                }
+                low = low_end;
+                high = high_start;
+            }
+        }
+    }
+
+    None
+}
+
+/// The least and most the rest of a set could add up to, given the first and
+/// last positions of a pick so far and how many entries it has, or `None` if
+/// there's no room for the rest.
+type Rest<'a> = &'a dyn Fn(usize, usize, usize) -> Option<(i64, i64)>;
+
+/// Every possible half of a set of entries, `size` entries each.
+struct Halves {
+    size: usize,
+    /// Whether these are first halves, which can always start earlier.
+    first: bool,
+    /// The positions of each half's entries, `size` at a time.
+    positions: Vec<usize>,
+    /// The sum of each half and its number, in order of sum.
+    sums: Vec<(i64, usize)>,
+}
+
+impl Halves {
+    /// Lists the halves from `start` on, leaving out any that the rest of a
+    /// set couldn't make up the total with.
+    fn list(values: &[i64], size: usize, start: usize, total: i64, rest: Rest) -> Halves {
+        let mut halves = Halves {
+            size,
+            first: start == 0,
+            positions: Vec::new(),
+            sums: Vec::new(),
+        };
+        halves.extend(values, start, 0, total, rest, &mut Vec::with_capacity(size));
+        halves.sums.sort_unstable();
+        halves
+    }
+
+    fn extend(
+        &mut self,
+        values: &[i64],
+        start: usize,
+        sum: i64,
+        total: i64,
+        rest: Rest,
+        pick: &mut Vec<usize>,
+    ) {
+        if pick.len() == self.size {
+            self.sums.push((sum, self.sums.len()));
+            self.positions.extend_from_slice(pick);
+            return;
+        }
+
+        for idx in start..values.len() {
+            // an earlier entry the same does as well, except to start a
+            // second half, which should start as late as it can
+            let can_skip = self.first || !pick.is_empty();
+            if can_skip && idx > start && values[idx] == values[idx - 1] {
+                continue;
+            }
+            let first = pick.first().copied().unwrap_or(idx);
+            let (least, most) = match rest(first, idx, pick.len() + 1) {
+                Some(bounds) => bounds,
+                None => break,
+            };
+            let sum = sum + values[idx];
+            if sum + least > total {
+                break;
+            }
+            if sum + most < total {
+                continue;
+            }
+
+            pick.push(idx);
+            self.extend(values, idx + 1, sum, total, rest, pick);
+            pick.pop();
+        }
+    }
+
+    fn pick(&self, id: usize) -> &[usize] {
+        &self.positions[id * self.size..(id + 1) * self.size]
+    }
+}
+
+/// The smallest total with a pair and a triplet that can't be mistaken for
+/// others.
+const MIN_TOTAL: i32 = 9;
+/// The largest total to make up reports for, as every entry more than half
+/// of it is considered for the report.
+const MAX_TOTAL: i32 = 10_000_000;
+
+/// Makes up an expense report with exactly one pair and one triplet of
+/// entries that sum to `total`.
+///
+/// Apart from the triplet and the smaller of the pair, every entry is more
+/// than half of `total`, so they can only add up to it with small entries.
+/// Those sums are avoided, which limits the report to about `total / 2`
+/// entries.
+fn generate_report(rng: &mut Rng, size: usize, total: i32) -> String {
+    let third = total as i64 / 3;
+    let (small, pair) = loop {
+        let a = rng.range(1..=third) as i32;
+        let (x, y) = (rng.range(1..=third) as i32, rng.range(1..=third) as i32);
+        let small = [a, x, y, total - x - y];
+
+        // the entries are different, and no other pair or triplet works
+        let distinct: HashSet<_> = small.iter().collect();
+        let sums_to_total = |entries: &[i32]| entries.iter().sum::<i32>() == total;
+        let other_triplets = [[a, x, y], [a, x, small[3]], [a, y, small[3]]];
+        let with_pair = [x + y, x + small[3], y + small[3]];
+        if distinct.len() == 4
+            && !other_triplets.iter().any(|t| sums_to_total(t))
+            && !with_pair.contains(&a)
+        {
+            break (small, total - a);
+        }
+    };
+
+    // big entries can't make `total` with two of the small ones
+    let mut taken: HashSet<i32> = small.iter().copied().collect();
+    taken.insert(pair);
+    for (idx, a) in small.iter().enumerate() {
+        taken.insert(total - a);
+        for b in &small[idx + 1..] {
+            taken.insert(total - a - b);
+        }
+    }
+
+    let mut entries: Vec<i32> = small.to_vec();
+    entries.push(pair);
+    let mut big: Vec<i32> = (total / 2 + 1..total)
+        .filter(|entry| !taken.contains(entry))
+        .collect();
+    rng.shuffle(&mut big);
+    entries.extend(big.into_iter().take(size.saturating_sub(entries.len())));
+    rng.shuffle(&mut entries);
+
+    entries.iter().map(|entry| format!("{}\n", entry)).collect()
+}
+
+const EXAMPLE: &str = "\
+1721
+979
+366
+299
+675
+1456
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::check_agreement;
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day1>(Part::BOTH);
+    }
+
+    #[test]
+    fn any_number_of_entries() {
+        let values = Day1::parse(EXAMPLE).unwrap();
+        let listed = [1721, 979, 366, 299, 675, 1456];
+        for count in 1..=6 {
+            let total = listed[..count].iter().sum();
+            let found = expenses(&values, count, total).unwrap();
+            assert_eq!(found.entries.len(), count);
+            assert_eq!(found.entries.iter().sum::<i64>(), total);
+            let product: i128 = found.entries.iter().map(|entry| *entry as i128).product();
+            assert_eq!(found.product, product);
+        }
+        assert_eq!(
+            expenses(&values, 4, 1),
+            Err(Error::NoSolution(
+                "no set of 4 entries sums to 1".to_string()
+            ))
+        );
+
+        // a repeated entry can be used once for each time it's there
+        assert_eq!(find_entries(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+        assert_eq!(find_entries(&[1010], 2, 2020), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        type Case = (Vec<i64>, usize, i64);
+        let brute_force = |(values, count, total): &Case| {
+            (0..1u32 << values.len()).any(|mask| {
+                let chosen = values
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| mask & (1 << idx) != 0);
+                mask.count_ones() as usize == *count
+                    && chosen.map(|(_, value)| value).sum::<i64>() == *total
+            })
+        };
+        let search = |(values, count, total): &Case| match find_entries(values, *count, *total) {
+            Some(entries) => {
+                // each entry has to come from a different line
+                let mut left = values.clone();
+                for entry in &entries {
+                    let idx = left.iter().position(|value| value == entry).unwrap();
+                    left.remove(idx);
+                }
+                entries.len() == *count && entries.iter().sum::<i64>() == *total
+            }
+            None => false,
+        };
+
+        check_agreement(
+            300,
+            12,
+            |rng, size| {
+                let mut values: Vec<i64> = (0..size).map(|_| rng.range(-10..=10)).collect();
+                values.sort_unstable();
+                (values, 1 + rng.below(6), rng.range(-20..=20))
+            },
+            brute_force,
+            search,
+        );
+    }
+
+    #[test]
+    fn generated_with_total() {
+        let mut overrides = Params::new();
+        for total in ["9", "100", "2020"] {
+            overrides.set("total", total);
+            let input = Day1::generate_with(&mut Rng::new(1), 50, &overrides)
+                .unwrap()
+                .unwrap();
+            let values = Day1::parse(&input).unwrap();
+            for count in 2..=3 {
+                let found = expenses(&values, count, total.parse().unwrap()).unwrap();
+                assert_eq!(found.entries.len(), count);
+            }
+        }
+
+        overrides.set("total", "8");
+        assert!(Day1::generate_with(&mut Rng::new(1), 50, &overrides).is_err());
+        overrides.set("total", "20000000");
+        assert!(Day1::generate_with(&mut Rng::new(1), 50, &overrides).is_err());
+    }
+
+    #[test]
+    fn big_reports() {
+        let total = 1_000_000;
+        let report = generate_report(&mut Rng::new(1), 100_000, total as i32);
+        let values = Day1::parse(&report).unwrap();
+        assert_eq!(values.len(), 100_000);
+
+        for count in 2..=6 {
+            match expenses(&values, count, total) {
+                Ok(found) => assert_eq!(found.entries.iter().sum::<i64>(), total),
+                // there's always a pair and a triplet
+                Err(error) => assert!(count > 3, "{}", error),
+            }
+        }
+    }
+
+    #[test]
+    fn no_solution_in_big_reports() {
+        // all even, so no sum is odd, but the bounds can't tell
+        let evens = |n: i64| -> Vec<i64> { (0..n).map(|i| 2 * i).collect() };
+        let values = evens(2000);
+        assert_eq!(find_entries(&values, 4, 4001), None);
+        assert!(find_entries(&values, 4, 4000).is_some());
+
+        let values = evens(200);
+        for count in 5..=6 {
+            assert_eq!(find_entries(&values, count, 401), None);
+            let found = find_entries(&values, count, 400).unwrap();
+            assert_eq!(found.iter().sum::<i64>(), 400);
+        }
+    }
+
+    #[test]
+    fn repeated_entries() {
+        assert_eq!(Day1::check(EXAMPLE), Vec::new());
+
+        let errors = Day1::check("1721\n979\nx\n1721\n979\n");
+        let lines: Vec<_> = errors
+            .iter()
+            .map(|error| error.to_string().lines().next().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "could not parse input: line 3, column 1: expected a number, found `x`",
+                "could not parse input: line 4, column 1: 1721 is already on line 1",
+                "could not parse input: line 5, column 1: 979 is already on line 2",
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day2;
+
+/// A line of the password database: the policy and the password it applies to.
+pub struct Entry {
+    pub param: char,
+    pub a: usize,
+    pub b: usize,
+    pub password: String,
+}
+
+impl Solver for Day2 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Password Philosophy";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("2"),
+        part_two: Some("1"),
+        params: &[],
+    }];
+
+    /// Lines of the password database.
+    const INPUT_SIZE: usize = 1000;
+
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Vec<Entry>, Error> {
+        parse::lines(input).map(parse_entry).collect()
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, parse_entry)
+    }
+
+    fn part_one(entries: &Vec<Entry>, _params: &Params) -> Result<Answer, Error> {
+        Ok(count_valid(entries, valid_day2a_password).into())
+    }
+
+    fn part_two(entries: &Vec<Entry>, _params: &Params) -> Result<Answer, Error> {
+        Ok(count_valid(entries, valid_day2b_password).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let letter = (b'a' + rng.below(26) as u8) as char;
+            let length = rng.range(2..=20) as usize;
+            let a = rng.range(1..=length as i64 - 1) as usize;
+            let b = rng.range(a as i64 + 1..=length as i64) as usize;
+
+            // use the policy's letter a lot, so that some passwords are valid
+            let password: String = (0..length)
+                .map(|_| match rng.chance(0.4) {
+                    true => letter,
+                    false => (b'a' + rng.below(26) as u8) as char,
+                })
+                .collect();
+            input.push_str(&format!("{}-{} {}: {}\n", a, b, letter, password));
+        }
+
+        Some(input)
+    }
+}
+
+pub fn valid_day2a_password(
+    password: &str,
+    param: char,
+    min_count: usize,
+    max_count: usize,
+) -> bool {
+    let mut counts = HashMap::new();
+
+    for c in password.chars() {
+        let counter = counts.entry(c).or_insert(0);
+        *counter += 1;
+    }
+
+    let char_count = counts.get(&param).copied().unwrap_or(0);
+
+    (min_count..=max_count).contains(&char_count)
+}
+
+pub fn valid_day2b_password(password: &str, param: char, pos_a: usize, pos_b: usize) -> bool {
+    let has_param = |pos: usize| pos >= 1 && password.chars().nth(pos - 1) == Some(param);
+    has_param(pos_a) ^ has_param(pos_b)
+}
+
+fn parse_entry(line: Line) -> Result<Entry, Error> {
+    let parts: Vec<&str> = line.text.split_whitespace().collect();
+    if parts.len() != 3 {
+        return Err(line.error(line.text, "expected `<a>-<b> <letter>: <password>`"));
+    }
+
+    let (a, b) = match parts[0].find('-') {
+        Some(idx) => (&parts[0][..idx], &parts[0][idx + 1..]),
+        None => return Err(line.error(parts[0], "expected a range like `1-3`")),
+    };
+    let a = line.parse::<usize>(a)?;
+    let b = line.parse::<usize>(b)?;
+
+    let param = match parts[1].strip_suffix(':') {
+        Some(letter) if letter.chars().count() == 1 => letter.chars().next().unwrap(),
+        _ => return Err(line.error(parts[1], "expected a single letter followed by `:`")),
+    };
+    let password = parts[2].to_string();
+
+    Ok(Entry {
+        param,
+        a,
+        b,
+        password,
+    })
+}
+
+pub fn count_valid(entries: &[Entry], val_fn: fn(&str, char, usize, usize) -> bool) -> usize {
+    entries
+        .iter()
+        .filter(|e| val_fn(&e.password, e.param, e.a, e.b))
+        .count()
+}
+
+const EXAMPLE: &str = "\
+1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day2>(Part::BOTH);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::log;
+use crate::params::{Param, Params};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Toboggan Trajectory";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("7"),
+        part_two: Some("336"),
+        params: &[],
+    }];
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "slopes",
+            part: Some(Part::One),
+            default: "3,1",
+            help: "`right,down` slopes, separated by spaces",
+        },
+        Param {
+            name: "slopes",
+            part: Some(Part::Two),
+            default: "1,1 3,1 5,1 7,1 1,2",
+            help: "`right,down` slopes, separated by spaces",
+        },
+    ];
+
+    /// Rows of the map, which is 31 squares wide.
+    const INPUT_SIZE: usize = 323;
+
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Grid<bool>, Error> {
+        read_map(input)
+    }
+
+    fn part_one(map: &Grid<bool>, params: &Params) -> Result<Answer, Error> {
+        let offsets = parse_slopes(&params.get::<String>("slopes")?)?;
+        Ok(product_of_trees(map, &offsets).into())
+    }
+
+    fn part_two(map: &Grid<bool>, params: &Params) -> Result<Answer, Error> {
+        let offsets = parse_slopes(&params.get::<String>("slopes")?)?;
+        Ok(product_of_trees(map, &offsets).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let map = Grid::from_rows(
+            (0..size)
+                .map(|_| (0..31).map(|_| rng.chance(0.2)).collect())
+                .collect(),
+        );
+        Some(map.render(|tree| if *tree { '#' } else { '.' }))
+    }
+}
+
+/// Parses slopes written as `right,down`, separated by spaces.
+fn parse_slopes(text: &str) -> Result<Vec<(usize, usize)>, Error> {
+    text.split_whitespace()
+        .map(|slope| {
+            let offsets = slope
+                .split_once(',')
+                .and_then(|(right, down)| Some((right.parse().ok()?, down.parse().ok()?)));
+            match offsets {
+                Some((right, down)) if down > 0 => Ok((right, down)),
+                _ => Err(Error::InvalidParam(format!(
+                    "expected a slope like `3,1`, found `{}`",
+                    slope
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Multiplies together the number of trees hit on each of the slopes, given
+/// as `(right, down)`.
+fn product_of_trees(map: &Grid<bool>, offsets: &[(usize, usize)]) -> u64 {
+    let mut prod: u64 = 1;
+    for (col_offset, row_offset) in offsets.iter() {
+        let n_trees = count_trees(map, *row_offset, *col_offset);
+        log!("{} {} {}", col_offset, row_offset, n_trees);
+        prod *= n_trees;
+    }
+
+    prod
+}
+
+fn read_map(input: &str) -> Result<Grid<bool>, Error> {
+    let cell = |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    };
+    Grid::parse(input, cell, "expected `.` or `#`")
+}
+
+pub fn count_trees(map: &Grid<bool>, row_offset: usize, col_offset: usize) -> u64 {
+    log!("height {}, width {}", map.height(), map.width());
+
+    // the map repeats to the right, so the column can keep going
+    let mut r = 0;
+    let mut c = 0;
+
+    let mut n_trees = 0;
+    while r < map.height() {
+        if *map.get_wrapping(r as isize, c as isize) {
+            n_trees += 1;
+        }
+
+        r += row_offset;
+        c += col_offset;
+    }
+
+    log!("hit {} trees", n_trees);
+    n_trees
+}
+
+const EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day3>(Part::BOTH);
+    }
+
+    #[test]
+    fn trees_on_each_slope() {
+        let map = read_map(EXAMPLE).unwrap();
+        assert_eq!(count_trees(&map, 1, 1), 2);
+        assert_eq!(count_trees(&map, 1, 3), 7);
+        assert_eq!(count_trees(&map, 1, 5), 3);
+        assert_eq!(count_trees(&map, 1, 7), 4);
+        assert_eq!(count_trees(&map, 2, 1), 2);
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Passport Processing";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("2"),
+            part_two: Some("2"),
+            params: &[],
+        },
+        Example {
+            input: INVALID_PASSPORTS,
+            part_one: None,
+            part_two: Some("0"),
+            params: &[],
+        },
+        Example {
+            input: VALID_PASSPORTS,
+            part_one: None,
+            part_two: Some("4"),
+            params: &[],
+        },
+    ];
+
+    /// Passports in the batch file.
+    const INPUT_SIZE: usize = 290;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        read_passports(input)
+    }
+
+    fn part_one(passports: &Vec<String>, _params: &Params) -> Result<Answer, Error> {
+        Ok(count_valid(passports, false).into())
+    }
+
+    fn part_two(passports: &Vec<String>, _params: &Params) -> Result<Answer, Error> {
+        Ok(count_valid(passports, true).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let passports: Vec<_> = (0..size).map(|_| generate_passport(rng)).collect();
+        Some(passports.join("\n"))
+    }
+}
+
+pub fn valid_value(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => match value.parse::<u16>() {
+            Ok(n) => (1920..=2002).contains(&n),
+            Err(_) => false,
+        },
+        "iyr" => match value.parse::<u16>() {
+            Ok(n) => (2010..=2020).contains(&n),
+            Err(_) => false,
+        },
+        "eyr" => match value.parse::<u16>() {
+            Ok(n) => (2020..=2030).contains(&n),
+            Err(_) => false,
+        },
+        "hgt" => {
+            if let Some(height) = value.strip_suffix("cm") {
+                match height.parse::<u16>() {
+                    Ok(height) => (150..=193).contains(&height),
+                    Err(_) => false,
+                }
+            } else if let Some(height) = value.strip_suffix("in") {
+                match height.parse::<u16>() {
+                    Ok(height) => (59..=76).contains(&height),
+                    Err(_) => false,
+                }
+            } else {
+                false
+            }
+        }
+        "hcl" => Regex::new(r"^#[0-9a-f]{6}$").unwrap().is_match(value),
+        "ecl" => matches!(value, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"),
+        "pid" => Regex::new(r"^[0-9]{9}$").unwrap().is_match(value),
+        _ => true,
+    }
+}
+
+pub fn valid_passport(passport: &str, validate_fields: bool) -> bool {
+    let required_fields: HashSet<_> = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
+        .iter()
+        .cloned()
+        .collect();
+
+    let mut fields_present = HashSet::new();
+    for part in passport.split_whitespace() {
+        let (key, value) = part.split_once(':').unwrap_or((part, ""));
+
+        if validate_fields && !valid_value(key, value) {
+            return false;
+        }
+
+        fields_present.insert(key);
+    }
+
+    required_fields.is_subset(&fields_present)
+}
+
+/// Collects the passports, which are separated by blank lines.
+fn read_passports(input: &str) -> Result<Vec<String>, Error> {
+    parse::paragraphs(input)
+        .map(|passport| {
+            for line in &passport.lines {
+                for field in line.text.split_whitespace() {
+                    if !field.contains(':') {
+                        return Err(line.error(field, "expected `key:value`"));
+                    }
+                }
+            }
+            Ok(passport.text())
+        })
+        .collect()
+}
+
+fn count_valid(passports: &[String], validate_fields: bool) -> usize {
+    passports
+        .iter()
+        .filter(|passport| valid_passport(passport, validate_fields))
+        .count()
+}
+
+/// Makes up a passport, which might be missing fields or have bad values,
+/// with the fields in a random order over a few lines.
+fn generate_passport(rng: &mut Rng) -> String {
+    let mut fields = Vec::new();
+    for key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+        if rng.chance(0.1) {
+            continue;
+        }
+
+        // most values are valid, but some are just out of range
+        let valid = !rng.chance(0.1);
+        let year = |rng: &mut Rng, start: i64, end: i64| match (valid, rng.chance(0.5)) {
+            (true, _) => rng.range(start..=end),
+            (false, true) => start - rng.range(1..=20),
+            (false, false) => end + rng.range(1..=20),
+        };
+        let value = match *key {
+            "byr" => year(rng, 1920, 2002).to_string(),
+            "iyr" => year(rng, 2010, 2020).to_string(),
+            "eyr" => year(rng, 2020, 2030).to_string(),
+            "hgt" => match (valid, rng.chance(0.5)) {
+                (true, true) => format!("{}cm", rng.range(150..=193)),
+                (true, false) => format!("{}in", rng.range(59..=76)),
+                (false, true) => format!("{}cm", rng.range(100..=149)),
+                (false, false) => rng.range(59..=193).to_string(),
+            },
+            "hcl" => {
+                let colour: String = (0..6)
+                    .map(|_| *rng.choose(&['0', '3', '7', 'a', 'c', 'f']))
+                    .collect();
+                match valid {
+                    true => format!("#{}", colour),
+                    false => colour,
+                }
+            }
+            "ecl" => {
+                let colours = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+                match valid {
+                    true => rng.choose(&colours).to_string(),
+                    false => rng.choose(&["xry", "zzz", "lzr"]).to_string(),
+                }
+            }
+            "pid" => {
+                let length = if valid { 9 } else { *rng.choose(&[8, 10]) };
+                (0..length).map(|_| rng.below(10).to_string()).collect()
+            }
+            _ => rng.range(50..=350).to_string(),
+        };
+        fields.push(format!("{}:{}", key, value));
+    }
+    rng.shuffle(&mut fields);
+
+    let mut passport = String::new();
+    for (idx, field) in fields.iter().enumerate() {
+        passport.push_str(field);
+        let last = idx + 1 == fields.len();
+        passport.push(if last || rng.chance(0.3) { '\n' } else { ' ' });
+    }
+
+    passport
+}
+
+const EXAMPLE: &str = "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+const INVALID_PASSPORTS: &str = "\
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+
+const VALID_PASSPORTS: &str = "\
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day4>(Part::BOTH);
+    }
+
+    #[test]
+    fn field_values() {
+        assert!(valid_value("byr", "2002"));
+        assert!(!valid_value("byr", "2003"));
+        assert!(valid_value("hgt", "60in"));
+        assert!(valid_value("hgt", "190cm"));
+        assert!(!valid_value("hgt", "190in"));
+        assert!(!valid_value("hgt", "190"));
+        assert!(valid_value("hcl", "#123abc"));
+        assert!(!valid_value("hcl", "#123abz"));
+        assert!(!valid_value("hcl", "123abc"));
+        assert!(valid_value("ecl", "brn"));
+        assert!(!valid_value("ecl", "wat"));
+        assert!(valid_value("pid", "000000001"));
+        assert!(!valid_value("pid", "0123456789"));
+    }
+}
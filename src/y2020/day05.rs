@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Binary Boarding";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("820"),
+        part_two: None,
+        params: &[],
+    }];
+
+    /// Boarding passes, which are for consecutive seats apart from yours.
+    const INPUT_SIZE: usize = 800;
+
+    type Input = HashSet<u64>;
+
+    fn parse(input: &str) -> Result<HashSet<u64>, Error> {
+        parse::lines(input).map(parse_seat).collect()
+    }
+
+    /// Two passes for the same seat would count as one.
+    fn check(input: &str) -> Vec<Error> {
+        let mut errors = Vec::new();
+        let mut seen = HashMap::new();
+        for line in parse::lines(input) {
+            match parse_seat(line) {
+                Ok(id) => match seen.get(&id) {
+                    Some(first) => errors.push(line.error(
+                        line.text.trim(),
+                        format!("seat {} is already on line {}", id, first),
+                    )),
+                    None => {
+                        seen.insert(id, line.number);
+                    }
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+
+        errors
+    }
+
+    fn part_one(seats: &HashSet<u64>, _params: &Params) -> Result<Answer, Error> {
+        match seats.iter().max() {
+            Some(seat) => Ok((*seat).into()),
+            None => Err(Error::NoSolution(
+                "there are no boarding passes".to_string(),
+            )),
+        }
+    }
+
+    fn part_two(seats: &HashSet<u64>, _params: &Params) -> Result<Answer, Error> {
+        let (min_seat, max_seat) = match (seats.iter().min(), seats.iter().max()) {
+            (Some(min_seat), Some(max_seat)) => (*min_seat, *max_seat),
+            _ => {
+                return Err(Error::NoSolution(
+                    "there are no boarding passes".to_string(),
+                ))
+            }
+        };
+
+        for n in min_seat..=max_seat {
+            if !seats.contains(&n) {
+                return Ok(n.into());
+            }
+        }
+
+        Err(Error::NoSolution(format!(
+            "no empty seat between {} and {}",
+            min_seat, max_seat
+        )))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // there are 1024 seats, and yours is between two others
+        let size = size.clamp(2, 1022);
+        let first = rng.below(1024 - size);
+        let yours = first + 1 + rng.below(size - 1);
+
+        let mut seats: Vec<_> = (first..=first + size).filter(|s| *s != yours).collect();
+        rng.shuffle(&mut seats);
+
+        let pass = |seat: usize| -> String {
+            let row = (0..7)
+                .rev()
+                .map(|bit| if seat >> (bit + 3) & 1 == 1 { 'B' } else { 'F' });
+            let col = (0..3)
+                .rev()
+                .map(|bit| if seat >> bit & 1 == 1 { 'R' } else { 'L' });
+            row.chain(col).collect()
+        };
+        Some(seats.into_iter().map(|seat| pass(seat) + "\n").collect())
+    }
+}
+
+/// Checks that the line is a boarding pass before decoding it.
+pub fn parse_seat(line: Line) -> Result<u64, Error> {
+    let seat = line.text.trim();
+    for (idx, c) in seat.char_indices() {
+        if idx >= 10 {
+            return Err(line.error(&seat[idx..], "boarding passes have 10 characters"));
+        }
+
+        let expected = if idx < 7 { ['F', 'B'] } else { ['L', 'R'] };
+        if !expected.contains(&c) {
+            let message = format!("expected `{}` or `{}`", expected[0], expected[1]);
+            return Err(line.error(&seat[idx..idx + c.len_utf8()], message));
+        }
+    }
+    if seat.len() != 10 {
+        return Err(line.error(seat, "boarding passes have 10 characters"));
+    }
+
+    Ok(get_seat_id(seat))
+}
+
+/// Decodes a boarding pass (e.g. `FBFBBFFRLR`) into a seat id. Only for
+/// passes that `parse_seat` has checked, as anything else panics.
+fn get_seat_id(seat: &str) -> u64 {
+    if seat.len() != 10 {
+        panic!("got seat {} with length {}", seat, seat.len());
+    }
+
+    let (row, col) = seat.split_at(7);
+
+    // get row
+    let mut row_min: u64 = 0;
+    let mut row_max: u64 = 128;
+
+    for c in row.chars() {
+        let mid = (row_max + row_min) / 2;
+
+        match c {
+            'F' => {
+                row_max = mid;
+            }
+            'B' => {
+                row_min = mid;
+            }
+            _ => panic!("invalid char {}", c),
+        }
+    }
+
+    // get col
+    let mut col_min: u64 = 0;
+    let mut col_max: u64 = 8;
+
+    for c in col.chars() {
+        let mid = (col_max + col_min) / 2;
+
+        match c {
+            'L' => {
+                col_max = mid;
+            }
+            'R' => {
+                col_min = mid;
+            }
+            _ => panic!("invalid char {}", c),
+        }
+    }
+
+    assert_eq!(row_min, row_max - 1);
+    assert_eq!(col_min, col_max - 1);
+
+    row_min * 8 + col_min
+}
+
+const EXAMPLE: &str = "\
+FBFBBFFRLR
+BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day5>(Part::BOTH);
+    }
+
+    #[test]
+    fn seat_ids() {
+        assert_eq!(get_seat_id("FBFBBFFRLR"), 357);
+        assert_eq!(get_seat_id("BFFFBBFRRR"), 567);
+        assert_eq!(get_seat_id("FFFBBBFRRR"), 119);
+        assert_eq!(get_seat_id("BBFFBBFRLL"), 820);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Custom Customs";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("11"),
+        part_two: Some("6"),
+        params: &[],
+    }];
+
+    /// Groups of people answering the questions.
+    const INPUT_SIZE: usize = 480;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        read_groups(input)
+    }
+
+    fn part_one(groups: &Vec<String>, _params: &Params) -> Result<Answer, Error> {
+        Ok(groups
+            .iter()
+            .map(|group| count_group_any(group))
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part_two(groups: &Vec<String>, _params: &Params) -> Result<Answer, Error> {
+        Ok(groups
+            .iter()
+            .map(|group| count_group_all(group))
+            .sum::<usize>()
+            .into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut groups = Vec::new();
+        for _ in 0..size {
+            // questions the group is likely to answer yes to
+            let popular: Vec<char> = ('a'..='z').filter(|_| rng.chance(0.3)).collect();
+
+            let mut group = String::new();
+            for _ in 0..rng.range(1..=5) {
+                let mut person: String = ('a'..='z')
+                    .filter(|q| rng.chance(if popular.contains(q) { 0.8 } else { 0.05 }))
+                    .collect();
+                if person.is_empty() {
+                    person.push((b'a' + rng.below(26) as u8) as char);
+                }
+                group.push_str(&person);
+                group.push('\n');
+            }
+            groups.push(group);
+        }
+
+        Some(groups.join("\n"))
+    }
+}
+
+pub fn count_group_any(group: &str) -> usize {
+    let persons: Vec<HashSet<char>> = group
+        .split_whitespace()
+        .map(|person| person.chars().collect())
+        .collect();
+
+    // union all
+    let group_answers = persons.iter().fold(HashSet::new(), |acc, person| {
+        acc.union(person).cloned().collect()
+    });
+
+    group_answers.len()
+}
+
+pub fn count_group_all(group: &str) -> usize {
+    let persons: Vec<HashSet<char>> = group
+        .split_whitespace()
+        .map(|person| person.chars().collect())
+        .collect();
+
+    // intersect all
+    let first = persons[0].clone();
+
+    let group_answers = persons.iter().skip(1).fold(first, |acc, person| {
+        acc.intersection(person).cloned().collect()
+    });
+
+    group_answers.len()
+}
+
+/// Collects the answers of each group, which are separated by blank lines.
+fn read_groups(input: &str) -> Result<Vec<String>, Error> {
+    parse::paragraphs(input)
+        .map(|group| {
+            for line in &group.lines {
+                let text = line.text.trim();
+                for (idx, c) in text.char_indices() {
+                    if !c.is_ascii_lowercase() {
+                        let question = &text[idx..idx + c.len_utf8()];
+                        return Err(line.error(question, "expected a question from `a` to `z`"));
+                    }
+                }
+            }
+            Ok(group.text())
+        })
+        .collect()
+}
+
+const EXAMPLE: &str = "\
+abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day6>(Part::BOTH);
+    }
+}
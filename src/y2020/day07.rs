@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use regex::Regex;
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day7;
+
+/// Map from each bag to its contents (and how many of each it holds).
+pub type Rules = HashMap<String, HashMap<String, i32>>;
+
+impl Solver for Day7 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Handy Haversacks";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("4"),
+            part_two: Some("32"),
+            params: &[],
+        },
+        Example {
+            input: NESTED_EXAMPLE,
+            part_one: None,
+            part_two: Some("126"),
+            params: &[],
+        },
+    ];
+
+    /// Rules, one for each colour of bag, up to 594.
+    const INPUT_SIZE: usize = 594;
+
+    type Input = Rules;
+
+    fn parse(input: &str) -> Result<Rules, Error> {
+        read_rules(input)
+    }
+
+    fn part_one(bag_contents: &Rules, _params: &Params) -> Result<Answer, Error> {
+        day7a(bag_contents).map(Answer::from)
+    }
+
+    fn part_two(bag_contents: &Rules, _params: &Params) -> Result<Answer, Error> {
+        day7b(bag_contents).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_rules(rng, size))
+    }
+}
+
+fn read_rules(input: &str) -> Result<Rules, Error> {
+    let sub_bag = Regex::new(r"^(\d+) (.*) bags?$").unwrap();
+
+    let mut bag_contents: Rules = HashMap::new();
+
+    for line in parse::lines(input) {
+        let (name, contents) = line.split_once(" contain ")?;
+
+        let name = match name.trim().strip_suffix(" bags") {
+            Some(name) => name,
+            None => return Err(line.error(name, "expected `<colour> bags`")),
+        };
+
+        let contents = contents.trim();
+        if contents == "no other bags." {
+            bag_contents.insert(name.to_string(), HashMap::new());
+            continue;
+        }
+
+        let contents = match contents.strip_suffix('.') {
+            Some(contents) => contents,
+            None => return Err(line.error(contents, "expected the rule to end with `.`")),
+        };
+
+        let mut bags = HashMap::new();
+        for s in contents.split(',') {
+            let s = s.trim();
+            let caps = match sub_bag.captures(s) {
+                Some(caps) => caps,
+                None => return Err(line.error(s, "expected `<count> <colour> bags`")),
+            };
+
+            let bag_count = line.parse::<i32>(caps.get(1).unwrap().as_str())?;
+            let bag_type = caps.get(2).unwrap().as_str();
+
+            bags.insert(bag_type.to_string(), bag_count);
+        }
+
+        bag_contents.insert(name.to_string(), bags);
+    }
+
+    Ok(bag_contents)
+}
+
+pub fn day7a(bag_contents: &Rules) -> Result<usize, Error> {
+    // we build a map from bags to bags that contain it.
+    // then we start from the shiny gold bag and find all containers of it.
+
+    let mut bags: HashMap<&str, HashSet<String>> = HashMap::new();
+
+    for (name, contents) in bag_contents {
+        for bag_type in contents.keys() {
+            bags.entry(bag_type).or_default().insert(name.to_string());
+        }
+    }
+
+    let mut initial_set = match bags.get("shiny gold") {
+        Some(containers) => containers.clone(),
+        _ => return Err(Error::NoSolution("didn't find shiny gold bags".to_string())),
+    };
+
+    loop {
+        // for each bag in the set, find all bags that contain them and join.
+        let new_set = initial_set.iter().map(|bag| bags.get(bag.as_str())).fold(
+            initial_set.clone(),
+            |acc, c| match c {
+                Some(container) => acc.union(container).cloned().collect(),
+                _ => acc,
+            },
+        );
+
+        assert!(initial_set.is_subset(&new_set));
+
+        if new_set.len() == initial_set.len() {
+            break;
+        }
+
+        initial_set = new_set;
+    }
+
+    Ok(initial_set.len())
+}
+
+pub fn day7b(bag_contents: &Rules) -> Result<i32, Error> {
+    // we start from the shiny gold bag and find all its contents
+    // (as a graph search)
+
+    // depth-first search
+    let mut total_bag_count = 0;
+    let mut to_visit = VecDeque::new();
+    to_visit.push_back((1, "shiny gold"));
+
+    while let Some((count, bag)) = to_visit.pop_back() {
+        total_bag_count += count;
+
+        let contents = match bag_contents.get(bag) {
+            Some(contents) => contents,
+            None => return Err(Error::NoSolution(format!("no rule for {} bags", bag))),
+        };
+        for (bag_type, bag_count) in contents {
+            to_visit.push_back((bag_count * count, bag_type));
+        }
+    }
+
+    // subtract one for the initial shiny gold bag
+    Ok(total_bag_count - 1)
+}
+
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLOURS: [&str; 33] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "turquoise",
+    "violet",
+    "white",
+    "yellow",
+];
+
+/// Makes up rules where bags only hold bags that come later in some order,
+/// so that no bag ends up inside itself.
+///
+/// The shiny gold bag is near the end of the order, and the few bags after
+/// it hold at most two kinds of bag, so that counting what's inside it one
+/// bag at a time doesn't take forever.
+fn generate_rules(rng: &mut Rng, size: usize) -> String {
+    const INSIDE_GOLD: usize = 8;
+
+    let mut bags = Vec::new();
+    for adjective in &ADJECTIVES {
+        for colour in &COLOURS {
+            let bag = format!("{} {}", adjective, colour);
+            if bag != "shiny gold" {
+                bags.push(bag);
+            }
+        }
+    }
+    rng.shuffle(&mut bags);
+    let size = size.clamp(INSIDE_GOLD + 2, bags.len() + 1);
+    bags.truncate(size - 1);
+    let gold = size - 1 - INSIDE_GOLD;
+    bags.insert(gold, "shiny gold".to_string());
+
+    let mut rules = Vec::new();
+    for (idx, bag) in bags.iter().enumerate() {
+        let (max_kinds, max_count) = if idx >= gold { (2, 4) } else { (4, 5) };
+        let min_kinds = if idx == gold { 1 } else { 0 };
+        let n_kinds = rng.range(min_kinds..=max_kinds as i64) as usize;
+        let n_kinds = n_kinds.min(size - idx - 1);
+
+        // different kinds of bag from later on, making sure something holds
+        // the shiny gold bag
+        let mut contents = Vec::new();
+        if idx + 1 == gold || (idx < gold && rng.chance(0.02)) {
+            contents.push(gold);
+        }
+        while contents.len() < n_kinds {
+            let inner = idx + 1 + rng.below(size - idx - 1);
+            if !contents.contains(&inner) {
+                contents.push(inner);
+            }
+        }
+
+        let contents: Vec<_> = contents
+            .into_iter()
+            .map(|inner| match rng.range(1..=max_count) {
+                1 => format!("1 {} bag", bags[inner]),
+                count => format!("{} {} bags", count, bags[inner]),
+            })
+            .collect();
+        rules.push(match contents.is_empty() {
+            true => format!("{} bags contain no other bags.\n", bag),
+            false => format!("{} bags contain {}.\n", bag, contents.join(", ")),
+        });
+    }
+    rng.shuffle(&mut rules);
+
+    rules.concat()
+}
+
+const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+const NESTED_EXAMPLE: &str = "\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day7>(Part::BOTH);
+    }
+}
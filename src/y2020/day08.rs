@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+use crate::trace;
+
+pub struct Day8;
+
+#[derive(Clone)]
+pub enum Instruction {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Acc(v) => write!(f, "acc {:+}", v),
+            Instruction::Jmp(v) => write!(f, "jmp {:+}", v),
+            Instruction::Nop(v) => write!(f, "nop {:+}", v),
+        }
+    }
+}
+
+struct ProgramState {
+    idx: i32,
+    acc_value: i32,
+}
+
+impl Solver for Day8 {
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Handheld Halting";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("5"),
+        part_two: Some("8"),
+        params: &[],
+    }];
+
+    /// Instructions in the program.
+    const INPUT_SIZE: usize = 600;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+        parse::lines(input).map(parse_instruction).collect()
+    }
+
+    /// Every `jmp` has to land on an instruction, or just after the last
+    /// one to end the program.
+    fn check(input: &str) -> Vec<Error> {
+        let lines: Vec<_> = parse::lines(input).collect();
+        let mut errors = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            match parse_instruction(*line) {
+                Ok(Instruction::Jmp(offset)) => {
+                    let target = idx as i64 + offset as i64;
+                    let message = if target < 0 {
+                        "jumps to before the first instruction"
+                    } else if target > lines.len() as i64 {
+                        "jumps past the end of the program"
+                    } else {
+                        continue;
+                    };
+                    let argument = line.text.split_whitespace().nth(1).unwrap();
+                    errors.push(line.error(argument, message));
+                }
+                Ok(_) => (),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        errors
+    }
+
+    fn part_one(program: &Vec<Instruction>, _params: &Params) -> Result<Answer, Error> {
+        let (_, acc_value) = step_through_program(program);
+        Ok(acc_value.into())
+    }
+
+    fn part_two(program: &Vec<Instruction>, _params: &Params) -> Result<Answer, Error> {
+        day8b(program).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let program = generate_program(rng, size);
+        let lines = program.iter().map(|instr| format!("{}\n", instr));
+        Some(lines.collect())
+    }
+}
+
+fn parse_instruction(line: Line) -> Result<Instruction, Error> {
+    let parts: Vec<&str> = line.text.split_whitespace().collect();
+    if parts.len() != 2 {
+        return Err(line.error(line.text, "expected `<operation> <argument>`"));
+    }
+
+    let instr = parts[0];
+    let value = line.parse::<i32>(parts[1])?;
+
+    match instr {
+        "acc" => Ok(Instruction::Acc(value)),
+        "jmp" => Ok(Instruction::Jmp(value)),
+        "nop" => Ok(Instruction::Nop(value)),
+        _ => Err(line.error(instr, format!("unknown operation `{}`", instr))),
+    }
+}
+
+fn make_step(instrs: &[Instruction], state: &ProgramState) -> ProgramState {
+    match instrs[state.idx as usize] {
+        Instruction::Acc(v) => ProgramState {
+            idx: state.idx + 1,
+            acc_value: state.acc_value + v,
+        },
+        Instruction::Jmp(v) => ProgramState {
+            idx: state.idx.saturating_add(v),
+            acc_value: state.acc_value,
+        },
+        Instruction::Nop(_) => ProgramState {
+            idx: state.idx + 1,
+            acc_value: state.acc_value,
+        },
+    }
+}
+
+/// step through program, returning whether the program terminates
+/// and the final accumulator value. jumping to before the first instruction
+/// doesn't count as terminating.
+pub fn step_through_program(program: &[Instruction]) -> (bool, i32) {
+    let mut visited: HashSet<i32> = HashSet::new();
+
+    let mut state = ProgramState {
+        idx: 0,
+        acc_value: 0,
+    };
+    while !visited.contains(&state.idx) && state.idx >= 0 && state.idx < (program.len() as i32) {
+        visited.insert(state.idx);
+        trace!(
+            "step",
+            pc = state.idx,
+            acc = state.acc_value,
+            instruction = program[state.idx as usize].to_string()
+        );
+
+        state = make_step(program, &state);
+    }
+
+    if state.idx < 0 {
+        log!(
+            "jumped to before the first instruction! accumulator: {}",
+            state.acc_value
+        );
+        (false, state.acc_value)
+    } else if visited.contains(&state.idx) {
+        log!(
+            "executing repeated instruction! accumulator: {}",
+            state.acc_value
+        );
+        (false, state.acc_value)
+    } else {
+        log!("final accumulator state: {}", state.acc_value);
+        (true, state.acc_value)
+    }
+}
+
+pub fn day8b(program: &[Instruction]) -> Result<i32, Error> {
+    // brute force: replace jmp/nop instructions one by one
+
+    for (idx, instr) in program.iter().enumerate() {
+        let replacement = match instr {
+            Instruction::Acc(_) => continue,
+            Instruction::Jmp(v) => Instruction::Nop(*v),
+            Instruction::Nop(v) => Instruction::Jmp(*v),
+        };
+
+        let mut new_program = program.to_vec();
+        new_program[idx] = replacement;
+        trace!("patch", idx = idx);
+
+        if let (true, acc_value) = step_through_program(&new_program) {
+            return Ok(acc_value);
+        }
+    }
+
+    Err(Error::NoSolution(
+        "no single change makes the program terminate".to_string(),
+    ))
+}
+
+/// Makes up a program that loops forever, but terminates if exactly one
+/// `jmp` or `nop` is swapped back.
+///
+/// The program runs forward along a path, jumping over traps that jump back
+/// to an earlier part of the path. Then an instruction on the path is
+/// corrupted: either a `nop` pointing backwards becomes a `jmp`, or a `jmp`
+/// over traps becomes a `nop`. Every jump skips at least two traps, so that
+/// swapping the first trap after a corrupted `jmp` only leads to the next
+/// one. Swapping any other instruction that runs still ends up going
+/// backwards.
+fn generate_program(rng: &mut Rng, size: usize) -> Vec<Instruction> {
+    let size = size.max(3);
+    loop {
+        let mut program = vec![None; size];
+        let mut path = Vec::new();
+        let mut idx = 0;
+        while idx < size {
+            path.push(idx);
+            let (instr, next_idx) = match rng.below(4) {
+                0 if idx + 3 <= size => {
+                    let offset = rng.range(3..=5).min((size - idx) as i64);
+                    (Instruction::Jmp(offset as i32), idx + offset as usize)
+                }
+                1 if idx > 0 => (Instruction::Nop(-rng.range(1..=idx as i64) as i32), idx + 1),
+                _ => (Instruction::Acc(rng.range(-50..=50) as i32), idx + 1),
+            };
+            program[idx] = Some(instr);
+            idx = next_idx;
+        }
+
+        let corruptible: Vec<_> = path
+            .into_iter()
+            .filter(|idx| {
+                matches!(
+                    program[*idx],
+                    Some(Instruction::Jmp(_)) | Some(Instruction::Nop(_))
+                )
+            })
+            .collect();
+        if corruptible.is_empty() {
+            continue;
+        }
+
+        let corrupt = *rng.choose(&corruptible);
+        program[corrupt] = match program[corrupt].take() {
+            Some(Instruction::Jmp(v)) => Some(Instruction::Nop(v)),
+            Some(Instruction::Nop(v)) => Some(Instruction::Jmp(v)),
+            instr => instr,
+        };
+
+        return program
+            .into_iter()
+            .enumerate()
+            .map(|(idx, instr)| {
+                instr.unwrap_or_else(|| Instruction::Jmp(-rng.range(1..=idx as i64) as i32))
+            })
+            .collect();
+    }
+}
+
+const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day8>(Part::BOTH);
+    }
+
+    #[test]
+    fn unknown_operation() {
+        match Day8::parse("nop +0\nmul +3\n") {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (2, 1));
+                assert_eq!(error.text, "mul");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn checks() {
+        assert_eq!(Day8::check(EXAMPLE), Vec::new());
+
+        let errors: Vec<_> = Day8::check("jmp -5\nmul +1\nnop -9\njmp +3\njmp +1\n")
+            .into_iter()
+            .map(|error| match error {
+                Error::Parse(error) => (error.line, error.column, error.message),
+                error => panic!("unexpected error {:?}", error),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (1, 5, "jumps to before the first instruction".to_string()),
+                (2, 1, "unknown operation `mul`".to_string()),
+                (4, 5, "jumps past the end of the program".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn jumping_before_the_start() {
+        let program = Day8::parse("acc +3\njmp -5\n").unwrap();
+        assert_eq!(step_through_program(&program), (false, 3));
+        assert_eq!(Day8::solve("acc +3\njmp -5\n", Part::One), Ok(3.into()));
+        // the only way out is past the end
+        assert_eq!(
+            Day8::solve("acc +3\njmp -5\nnop -3\n", Part::Two),
+            Ok(3.into())
+        );
+
+        let far = Day8::parse("jmp +2147483647\njmp -2147483648\n").unwrap();
+        assert_eq!(step_through_program(&far), (true, 0));
+    }
+
+    #[test]
+    fn generated_programs_have_one_fix() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let program = generate_program(&mut rng, 3 + seed as usize % 40);
+            let fixes = (0..program.len())
+                .filter(|idx| {
+                    let mut fixed = program.clone();
+                    fixed[*idx] = match program[*idx] {
+                        Instruction::Acc(_) => return false,
+                        Instruction::Jmp(v) => Instruction::Nop(v),
+                        Instruction::Nop(v) => Instruction::Jmp(v),
+                    };
+                    step_through_program(&fixed).0
+                })
+                .count();
+            assert_eq!(fixes, 1, "seed {}", seed);
+        }
+    }
+}
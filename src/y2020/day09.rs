@@ -0,0 +1,274 @@
+use crate::error::Error;
+use crate::log;
+use crate::params::{Param, Params};
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Encoding Error";
+    // the example uses a preamble of 5 instead of 25
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("127"),
+        part_two: Some("62"),
+        params: &[("preamble", "5")],
+    }];
+    const PARAMS: &'static [Param] = &[Param {
+        name: "preamble",
+        part: None,
+        default: "25",
+        help: "how many earlier numbers each number can be the sum of",
+    }];
+
+    /// Numbers in the list, up to 1000.
+    const INPUT_SIZE: usize = 1000;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse::lines(input)
+            .map(|line| line.parse::<i64>(line.text))
+            .collect()
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, |line| line.parse::<i64>(line.text))
+    }
+
+    fn part_one(numbers: &Vec<i64>, params: &Params) -> Result<Answer, Error> {
+        find_bad_number(numbers, params.get("preamble")?).map(Answer::from)
+    }
+
+    fn part_two(numbers: &Vec<i64>, params: &Params) -> Result<Answer, Error> {
+        let bad_number = find_bad_number(numbers, params.get("preamble")?)?;
+        if numbers.iter().all(|n| *n >= 0) {
+            day9b_two_pointer(numbers, bad_number).map(Answer::from)
+        } else {
+            day9b(numbers, bad_number).map(Answer::from)
+        }
+    }
+
+    fn generate_with(rng: &mut Rng, size: usize, params: &Params) -> Result<Option<String>, Error> {
+        let preamble = match params.get("preamble")? {
+            preamble if preamble >= 2 => preamble,
+            preamble => {
+                return Err(Error::InvalidParam(format!(
+                    "need a preamble of at least 2 to make up numbers, not {}",
+                    preamble
+                )))
+            }
+        };
+        let numbers = generate_numbers(rng, size, preamble);
+        Ok(Some(numbers.iter().map(|n| format!("{}\n", n)).collect()))
+    }
+}
+
+/// part 1: find the first number that is not the sum of two of the
+/// `preamble_size` numbers before it (using brute force)
+pub fn find_bad_number(numbers: &[i64], preamble_size: usize) -> Result<i64, Error> {
+    for (idx, next_num) in numbers.iter().enumerate().skip(preamble_size) {
+        let window = &numbers[(idx - preamble_size)..idx];
+
+        // check for pair that sums
+        let mut has_sum = false;
+        for n in window {
+            for m in window {
+                if n != m && n + m == *next_num {
+                    has_sum = true;
+                }
+            }
+        }
+
+        if !has_sum {
+            log!("bad number: {}", next_num);
+            return Ok(*next_num);
+        }
+    }
+
+    Err(Error::NoSolution(
+        "every number is the sum of two earlier numbers".to_string(),
+    ))
+}
+
+pub fn day9b(numbers: &[i64], bad_number: i64) -> Result<i64, Error> {
+    let mut prefix_sums = vec![0];
+    let mut current_sum = 0;
+    for n in numbers {
+        current_sum += n;
+        prefix_sums.push(current_sum);
+    }
+
+    // brute force, going through all O(n^2) ranges.
+    for (upper_idx, upper_sum) in prefix_sums.iter().enumerate() {
+        for (lower_idx, lower_sum) in prefix_sums[..upper_idx].iter().enumerate() {
+            // the range must have at least two numbers
+            if upper_idx - lower_idx >= 2 && upper_sum - lower_sum == bad_number {
+                let range = &numbers[lower_idx..upper_idx];
+                let min = range.iter().min().unwrap();
+                let max = range.iter().max().unwrap();
+                log!("min: {}, max: {}", min, max);
+                return Ok(max + min);
+            }
+        }
+    }
+
+    Err(Error::NoSolution(format!(
+        "no contiguous range sums to {}",
+        bad_number
+    )))
+}
+
+/// Like `day9b`, but in O(n) by sliding a window along the numbers, which
+/// only works if none of them are negative: then adding a number never makes
+/// the sum smaller, and dropping one never makes it bigger.
+///
+/// For each end, the window starts as early as it can without the sum going
+/// over, so it finds the same range as `day9b`.
+pub fn day9b_two_pointer(numbers: &[i64], bad_number: i64) -> Result<i64, Error> {
+    let mut lower_idx = 0;
+    let mut sum = 0;
+    for (upper_idx, n) in numbers.iter().enumerate() {
+        sum += n;
+        while sum > bad_number && lower_idx < upper_idx {
+            sum -= numbers[lower_idx];
+            lower_idx += 1;
+        }
+
+        // the range must have at least two numbers
+        if sum == bad_number && upper_idx > lower_idx {
+            let range = &numbers[lower_idx..=upper_idx];
+            let min = range.iter().min().unwrap();
+            let max = range.iter().max().unwrap();
+            log!("min: {}, max: {}", min, max);
+            return Ok(max + min);
+        }
+    }
+
+    Err(Error::NoSolution(format!(
+        "no contiguous range sums to {}",
+        bad_number
+    )))
+}
+
+/// Makes up numbers where each one after the preamble is the sum of two of
+/// the numbers before it, except for one in the second half, which is the
+/// sum of a run of earlier numbers instead.
+///
+/// The sums use the smallest numbers available, but the numbers still
+/// roughly double with each preamble, so there are at most 1000 of them.
+fn generate_numbers(rng: &mut Rng, size: usize, preamble_size: usize) -> Vec<i64> {
+    let size = size.clamp(preamble_size + 3, 1000.max(preamble_size + 3));
+    'numbers: loop {
+        let mut numbers: Vec<i64> = (1..=2 * preamble_size as i64).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(preamble_size);
+
+        let bad_idx = size / 2 + rng.below(size - size / 2);
+        let bad_idx = bad_idx.max(preamble_size + 2);
+        for idx in preamble_size..size {
+            let window = &numbers[idx - preamble_size..idx];
+
+            if idx == bad_idx {
+                let is_pair_sum = |sum| {
+                    window
+                        .iter()
+                        .any(|n| window.iter().any(|m| n != m && n + m == sum))
+                };
+                let bad_number = (0..100).find_map(|_| {
+                    let length = rng.range(2..=17).min(idx as i64) as usize;
+                    let start = rng.below(idx - length + 1);
+                    let sum = numbers[start..start + length].iter().sum();
+                    Some(sum).filter(|sum| !is_pair_sum(*sum))
+                });
+                match bad_number {
+                    Some(bad_number) => numbers.push(bad_number),
+                    None => continue 'numbers,
+                }
+                continue;
+            }
+
+            let mut smallest = window.to_vec();
+            smallest.sort_unstable();
+            smallest.dedup();
+            smallest.truncate(6);
+            let a = rng.below(smallest.len());
+            let b = (a + 1 + rng.below(smallest.len() - 1)) % smallest.len();
+            numbers.push(smallest[a] + smallest[b]);
+        }
+
+        return numbers;
+    }
+}
+
+const EXAMPLE: &str = "\
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::check_agreement;
+    use crate::solver::{check_examples, Part, Solution};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day9>(Part::BOTH);
+    }
+
+    #[test]
+    fn generated_with_preamble() {
+        let mut overrides = Params::new();
+        overrides.set("preamble", "5");
+        let input = Day9.generate(40, 1, &overrides).unwrap().unwrap();
+        assert!(Day9::solve_with(&input, Part::Two, &overrides).is_ok());
+
+        overrides.set("preamble", "1");
+        assert!(Day9.generate(40, 1, &overrides).is_err());
+    }
+
+    #[test]
+    fn two_pointer_agrees() {
+        // some numbers (including zeros), and a sum to look for, which is
+        // usually the sum of a run of them
+        let generate = |rng: &mut Rng, size| {
+            let numbers: Vec<i64> = (0..size + 1).map(|_| rng.range(0..=20)).collect();
+            let start = rng.below(size);
+            let end = start + 2 + rng.below(size - start);
+            let bad_number = match rng.chance(0.8) {
+                true => numbers[start..end].iter().sum(),
+                false => rng.range(0..=100),
+            };
+            (numbers, bad_number)
+        };
+        check_agreement(
+            500,
+            50,
+            generate,
+            |(numbers, bad_number)| day9b(numbers, *bad_number),
+            |(numbers, bad_number)| day9b_two_pointer(numbers, *bad_number),
+        );
+    }
+}
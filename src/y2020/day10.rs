@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Adapter Array";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("35"),
+            part_two: Some("8"),
+            params: &[],
+        },
+        Example {
+            input: LARGER_EXAMPLE,
+            part_one: Some("220"),
+            part_two: Some("19208"),
+            params: &[],
+        },
+    ];
+
+    /// Adapters in the bag, or fewer if there'd be too many arrangements to count.
+    const INPUT_SIZE: usize = 100;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>, Error> {
+        read_day10(input)
+    }
+
+    fn part_one(joltages: &Vec<i32>, _params: &Params) -> Result<Answer, Error> {
+        let mut one_diffs = 0;
+        let mut three_diffs = 0;
+
+        for (idx, joltage) in joltages[1..].iter().enumerate() {
+            let diff = joltage - joltages[idx];
+            if diff == 1 {
+                one_diffs += 1;
+            } else if diff == 3 {
+                three_diffs += 1;
+            }
+        }
+
+        // for the device
+        three_diffs += 1;
+
+        log!("1s: {}, 3s: {}", one_diffs, three_diffs);
+        Ok((one_diffs * three_diffs).into())
+    }
+
+    fn part_two(joltages: &Vec<i32>, _params: &Params) -> Result<Answer, Error> {
+        // dynamic programming
+        // let V[i] be the number of paths that include (i.e. end with) joltage i.
+        // then V[i] = V[i-1] + V[i-2] + V[i-3].
+
+        let mut num_paths: HashMap<i32, u64> = HashMap::new();
+        num_paths.insert(0, 1);
+
+        for joltage in joltages[1..].iter() {
+            let mut sum = 0;
+
+            for diff in 1..4 {
+                sum += num_paths.get(&(joltage - diff)).copied().unwrap_or(0);
+            }
+
+            num_paths.insert(*joltage, sum);
+        }
+
+        Ok(num_paths[joltages.last().unwrap()].into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut joltages = generate_joltages(rng, size);
+        rng.shuffle(&mut joltages);
+        Some(joltages.iter().map(|j| format!("{}\n", j)).collect())
+    }
+}
+
+fn read_day10(input: &str) -> Result<Vec<i32>, Error> {
+    // read joltages and sort them
+    let mut joltages = vec![0];
+
+    for line in parse::lines(input) {
+        joltages.push(line.parse::<i32>(line.text)?);
+    }
+
+    joltages.sort_unstable();
+
+    Ok(joltages)
+}
+
+/// Makes up adapters that go up by 1 or 3 jolts, with at most four steps of
+/// 1 in a row, like the real inputs.
+///
+/// The number of arrangements is counted along the way, stopping before it
+/// gets too big to count.
+fn generate_joltages(rng: &mut Rng, size: usize) -> Vec<i32> {
+    let mut joltages = Vec::new();
+    // the arrangements ending at each of the last three joltages
+    let mut last = [(0, 1u64), (-1, 0), (-2, 0)];
+    let mut ones = 0;
+
+    while joltages.len() < size {
+        let step = if ones < 4 && rng.chance(0.6) { 1 } else { 3 };
+        let joltage = last[0].0 + step;
+        let arrangements = last
+            .iter()
+            .filter(|(j, _)| joltage - j <= 3)
+            .try_fold(0u64, |sum, (_, n)| sum.checked_add(*n));
+        match arrangements {
+            // leave room for the device at the end
+            Some(arrangements) if arrangements < u64::MAX / 4 => {
+                last = [(joltage, arrangements), last[0], last[1]];
+            }
+            _ => break,
+        }
+
+        ones = if step == 1 { ones + 1 } else { 0 };
+        joltages.push(joltage);
+    }
+
+    joltages
+}
+
+const EXAMPLE: &str = "\
+16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4
+";
+
+const LARGER_EXAMPLE: &str = "\
+28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day10>(Part::BOTH);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+use std::mem;
+
+use crate::automaton::{Automaton, Bounded, Rule};
+use crate::error::Error;
+use crate::grid::{Grid, Pos};
+use crate::log;
+use crate::params::Params;
+use crate::render::Frame;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day11;
+
+#[derive(Clone, PartialEq)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Solver for Day11 {
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Seating System";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("37"),
+        part_two: Some("26"),
+        params: &[],
+    }];
+
+    /// The width and height of the waiting area.
+    const INPUT_SIZE: usize = 90;
+
+    type Input = Grid<Seat>;
+
+    fn parse(input: &str) -> Result<Grid<Seat>, Error> {
+        read_seats(input)
+    }
+
+    fn part_one(layout: &Grid<Seat>, _params: &Params) -> Result<Answer, Error> {
+        let (neighbours, crowded) = rules(layout, Part::One);
+        Ok(run_until_stable(layout, neighbours, crowded).into())
+    }
+
+    fn part_two(layout: &Grid<Seat>, _params: &Params) -> Result<Answer, Error> {
+        let (neighbours, crowded) = rules(layout, Part::Two);
+        Ok(run_until_stable(layout, neighbours, crowded).into())
+    }
+
+    fn frames(layout: &Grid<Seat>, part: Part, _params: &Params) -> Result<Vec<Frame>, Error> {
+        let (neighbours, crowded) = rules(layout, part);
+        Ok(seat_frames(layout, neighbours, crowded))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let seats = generate_seats(rng, size);
+        Some(seats.render(|seat| if *seat { 'L' } else { '.' }))
+    }
+}
+
+fn read_seats(input: &str) -> Result<Grid<Seat>, Error> {
+    let cell = |c| match c {
+        'L' => Some(Seat::Empty),
+        '.' => Some(Seat::Floor),
+        _ => None,
+    };
+    Grid::parse(input, cell, "expected `L` or `.`")
+}
+
+/// Where the seats are, rather than the floor.
+fn seats(layout: &Grid<Seat>) -> Grid<bool> {
+    layout.map(|seat| *seat != Seat::Floor)
+}
+
+/// Which seats people look at, and how many of them being occupied is too
+/// crowded, for each part.
+fn rules(layout: &Grid<Seat>, part: Part) -> (Bounded, usize) {
+    match part {
+        Part::One => (Bounded::adjacent(&seats(layout)), 4),
+        Part::Two => (Bounded::line_of_sight(&seats(layout)), 5),
+    }
+}
+
+/// The people in the waiting area. Empty seats are taken if none of their
+/// neighbours are occupied, and people leave if `crowded` neighbours or more
+/// are occupied.
+fn seating(layout: &Grid<Seat>, neighbours: Bounded, crowded: usize) -> Automaton<Bounded> {
+    let occupied = layout
+        .iter()
+        .filter(|(_, seat)| **seat == Seat::Occupied)
+        .map(|(pos, _)| pos);
+    let rule = Rule::new(0..=0, 0..=crowded - 1);
+    Automaton::new(neighbours, rule, occupied)
+}
+
+/// Lets people sit down and get up until nobody moves any more, then counts
+/// the occupied seats.
+pub fn run_until_stable(layout: &Grid<Seat>, neighbours: Bounded, crowded: usize) -> usize {
+    let mut seats = seating(layout, neighbours, crowded);
+
+    let n_rounds = seats.run_until_stable();
+    log!("ran for {} rounds", n_rounds);
+    seats.population()
+}
+
+/// Draws the waiting area after each round, until nobody moves any more.
+fn seat_frames(layout: &Grid<Seat>, neighbours: Bounded, crowded: usize) -> Vec<Frame> {
+    let mut seats = seating(layout, neighbours, crowded);
+    let floor = layout.map(|seat| match seat {
+        Seat::Floor => '.',
+        _ => 'L',
+    });
+
+    seats
+        .history(None)
+        .into_iter()
+        .map(|occupied| {
+            let mut frame = floor.clone();
+            for pos in occupied {
+                frame[pos] = '#';
+            }
+            frame
+        })
+        .collect()
+}
+
+/// Makes up a waiting area where people settle down in both parts.
+///
+/// Random layouts often have clumps of seats that all fill up and then all
+/// empty out again forever, so some of the seats that are still changing are
+/// turned into floor until that stops.
+fn generate_seats(rng: &mut Rng, size: usize) -> Grid<bool> {
+    let rows = (0..size)
+        .map(|_| (0..size).map(|_| rng.chance(0.85)).collect())
+        .collect();
+    let mut seats = Grid::from_rows(rows);
+
+    loop {
+        let mut changing = unsettled(Bounded::adjacent(&seats), 4);
+        if changing.is_empty() {
+            changing = unsettled(Bounded::line_of_sight(&seats), 5);
+        }
+        if changing.is_empty() {
+            return seats;
+        }
+
+        let pos = *rng.choose(&changing);
+        seats[pos] = false;
+        for pos in changing {
+            if rng.chance(0.2) {
+                seats[pos] = false;
+            }
+        }
+    }
+}
+
+/// The seats that keep changing after people have had plenty of time to
+/// settle down, or that swap back and forth between two layouts.
+fn unsettled(neighbours: Bounded, crowded: usize) -> Vec<Pos> {
+    let rule = Rule::new(0..=0, 0..=crowded - 1);
+    let mut seats = Automaton::new(neighbours, rule, None);
+
+    let mut two_ago = HashSet::new();
+    let mut one_ago = HashSet::new();
+    for _ in 0..1000 {
+        if !seats.step() {
+            return Vec::new();
+        }
+        if *seats.live() == two_ago {
+            break;
+        }
+        two_ago = mem::replace(&mut one_ago, seats.live().clone());
+    }
+
+    seats
+        .live()
+        .symmetric_difference(&one_ago)
+        .copied()
+        .collect()
+}
+
+const EXAMPLE: &str = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day11>(Part::BOTH);
+    }
+
+    #[test]
+    fn frames_until_stable() {
+        let layout = Day11::parse(EXAMPLE).unwrap();
+        let frames = Day11::frames(&layout, Part::One, &Params::new()).unwrap();
+        assert_eq!(frames.len(), 6);
+        assert_eq!(
+            frames[0],
+            layout.map(|seat| if *seat == Seat::Floor { '.' } else { 'L' })
+        );
+        assert_eq!(frames[5].count(|c| *c == '#'), 37);
+    }
+}
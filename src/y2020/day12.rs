@@ -0,0 +1,215 @@
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day12;
+
+pub enum Angle {
+    AntiClockwise,
+    Around,
+    Clockwise,
+}
+
+pub enum Action {
+    North(i32),
+    South(i32),
+    East(i32),
+    West(i32),
+    Rotate(Angle),
+    Forward(i32),
+}
+
+impl Solver for Day12 {
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Rain Risk";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("25"),
+        part_two: Some("286"),
+        params: &[],
+    }];
+
+    /// Navigation instructions, up to 10000 so that the distances fit.
+    const INPUT_SIZE: usize = 780;
+
+    type Input = Vec<Action>;
+
+    fn parse(input: &str) -> Result<Vec<Action>, Error> {
+        parse::lines(input).map(parse_action).collect()
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, parse_action)
+    }
+
+    fn part_one(actions: &Vec<Action>, _params: &Params) -> Result<Answer, Error> {
+        Ok(day12a(actions).into())
+    }
+
+    fn part_two(actions: &Vec<Action>, _params: &Params) -> Result<Answer, Error> {
+        Ok(day12b(actions).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size.min(10000) {
+            let instruction = match rng.below(10) {
+                0 => format!("{}{}", rng.choose(&["L", "R"]), rng.choose(&[90, 180, 270])),
+                1..=4 => format!("{}{}", rng.choose(&["N", "S", "E", "W"]), rng.range(1..=5)),
+                _ => format!("F{}", rng.range(1..=100)),
+            };
+            input.push_str(&instruction);
+            input.push('\n');
+        }
+
+        Some(input)
+    }
+}
+
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+fn forward(dir: &Direction, distance: &i32, x: &mut i32, y: &mut i32) {
+    match dir {
+        Direction::North => *y += distance,
+        Direction::South => *y -= distance,
+        Direction::East => *x += distance,
+        Direction::West => *x -= distance,
+    }
+}
+
+fn turn(dir: &Direction, turn_angle: &Angle) -> Direction {
+    match (turn_angle, dir) {
+        (Angle::AntiClockwise, Direction::North) => Direction::West,
+        (Angle::AntiClockwise, Direction::West) => Direction::South,
+        (Angle::AntiClockwise, Direction::South) => Direction::East,
+        (Angle::AntiClockwise, Direction::East) => Direction::North,
+        (Angle::Clockwise, Direction::North) => Direction::East,
+        (Angle::Clockwise, Direction::West) => Direction::North,
+        (Angle::Clockwise, Direction::South) => Direction::West,
+        (Angle::Clockwise, Direction::East) => Direction::South,
+        (Angle::Around, Direction::North) => Direction::South,
+        (Angle::Around, Direction::West) => Direction::East,
+        (Angle::Around, Direction::South) => Direction::North,
+        (Angle::Around, Direction::East) => Direction::West,
+    }
+}
+
+fn parse_action(line: Line) -> Result<Action, Error> {
+    let text = line.text.trim();
+    let action = match text.chars().next() {
+        Some(c) => &text[..c.len_utf8()],
+        None => return Err(line.error(text, "expected an action")),
+    };
+    let arg_text = &text[action.len()..];
+    let arg = line.parse::<i32>(arg_text)?;
+    let bad_angle = || line.error(arg_text, "can only turn by 90, 180 or 270 degrees");
+
+    Ok(match action {
+        "N" => Action::North(arg),
+        "S" => Action::South(arg),
+        "E" => Action::East(arg),
+        "W" => Action::West(arg),
+        "L" => match arg {
+            90 => Action::Rotate(Angle::AntiClockwise),
+            180 => Action::Rotate(Angle::Around),
+            270 => Action::Rotate(Angle::Clockwise),
+            _ => return Err(bad_angle()),
+        },
+        "R" => match arg {
+            90 => Action::Rotate(Angle::Clockwise),
+            180 => Action::Rotate(Angle::Around),
+            270 => Action::Rotate(Angle::AntiClockwise),
+            _ => return Err(bad_angle()),
+        },
+        "F" => Action::Forward(arg),
+        _ => return Err(line.error(action, format!("unknown action `{}`", action))),
+    })
+}
+
+pub fn day12a(actions: &[Action]) -> i32 {
+    let mut x = 0;
+    let mut y = 0;
+
+    let mut dir = Direction::East;
+
+    for action in actions {
+        match action {
+            Action::Forward(n) => forward(&dir, n, &mut x, &mut y),
+            Action::North(n) => y += n,
+            Action::South(n) => y -= n,
+            Action::East(n) => x += n,
+            Action::West(n) => x -= n,
+            Action::Rotate(turn_angle) => dir = turn(&dir, turn_angle),
+        }
+    }
+
+    log!("x: {}, y: {}", x, y);
+    x.abs() + y.abs()
+}
+
+pub fn day12b(actions: &[Action]) -> i32 {
+    let mut x = 0;
+    let mut y = 0;
+
+    let mut waypoint_x = 10;
+    let mut waypoint_y = 1;
+
+    for action in actions {
+        match action {
+            Action::North(n) => waypoint_y += n,
+            Action::South(n) => waypoint_y -= n,
+            Action::East(n) => waypoint_x += n,
+            Action::West(n) => waypoint_x -= n,
+            Action::Forward(n) => {
+                x += waypoint_x * n;
+                y += waypoint_y * n;
+            }
+            Action::Rotate(turn_angle) => match turn_angle {
+                Angle::Around => {
+                    waypoint_x = -waypoint_x;
+                    waypoint_y = -waypoint_y;
+                }
+                Angle::Clockwise => {
+                    let tmp = waypoint_x;
+                    waypoint_x = waypoint_y;
+                    waypoint_y = -tmp;
+                }
+                Angle::AntiClockwise => {
+                    let tmp = waypoint_x;
+                    waypoint_x = -waypoint_y;
+                    waypoint_y = tmp;
+                }
+            },
+        }
+    }
+
+    log!("x: {}, y: {}", x, y);
+    x.abs() + y.abs()
+}
+
+const EXAMPLE: &str = "\
+F10
+N3
+F7
+R90
+F11
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day12>(Part::BOTH);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+use crate::trace;
+
+pub struct Day13;
+
+/// The earliest departure time, and the bus ids (`None` for `x`).
+pub struct Notes {
+    pub arrival_time: u64,
+    pub bus_ids: Vec<Option<u64>>,
+}
+
+impl Solver for Day13 {
+    const DAY: u32 = 13;
+    const TITLE: &'static str = "Shuttle Search";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("295"),
+            part_two: Some("1068781"),
+            params: &[],
+        },
+        Example {
+            input: EXAMPLE_2,
+            part_one: None,
+            part_two: Some("3417"),
+            params: &[],
+        },
+        Example {
+            input: EXAMPLE_3,
+            part_one: None,
+            part_two: Some("754018"),
+            params: &[],
+        },
+        Example {
+            input: EXAMPLE_4,
+            part_one: None,
+            part_two: Some("779210"),
+            params: &[],
+        },
+        Example {
+            input: EXAMPLE_5,
+            part_one: None,
+            part_two: Some("1261476"),
+            params: &[],
+        },
+        Example {
+            input: EXAMPLE_6,
+            part_one: None,
+            part_two: Some("1202161486"),
+            params: &[],
+        },
+    ];
+
+    /// Places in the bus schedule, either a bus or `x`.
+    const INPUT_SIZE: usize = 60;
+
+    type Input = Notes;
+
+    fn parse(input: &str) -> Result<Notes, Error> {
+        let mut lines = parse::lines(input);
+
+        let line = match lines.next() {
+            Some(line) => line,
+            None => return Err(parse::end_of_input(input, "expected the arrival time")),
+        };
+        let arrival_time = line.parse::<u64>(line.text)?;
+
+        let line = match lines.next() {
+            Some(line) => line,
+            None => return Err(parse::end_of_input(input, "expected the bus ids")),
+        };
+        let mut bus_ids = Vec::new();
+        for id in line.text.trim().split(',') {
+            bus_ids.push(match id {
+                "x" => None,
+                _ => match line.parse::<u64>(id)? {
+                    0 => return Err(line.error(id, "bus ids must be positive")),
+                    id => Some(id),
+                },
+            });
+        }
+        if bus_ids.iter().all(Option::is_none) {
+            return Err(line.error(line.text, "expected at least one bus id"));
+        }
+
+        Ok(Notes {
+            arrival_time,
+            bus_ids,
+        })
+    }
+
+    fn part_one(notes: &Notes, _params: &Params) -> Result<Answer, Error> {
+        Ok(day13a(notes).into())
+    }
+
+    fn part_two(notes: &Notes, _params: &Params) -> Result<Answer, Error> {
+        Ok(day13b(notes).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let ids = generate_bus_ids(rng, size);
+        let ids: Vec<_> = ids
+            .iter()
+            .map(|id| id.map_or("x".to_string(), |id| id.to_string()))
+            .collect();
+        Some(format!(
+            "{}\n{}\n",
+            rng.range(100000..=1000000),
+            ids.join(",")
+        ))
+    }
+}
+
+pub fn day13a(notes: &Notes) -> u64 {
+    let arrival_time = notes.arrival_time;
+    let bus_ids: Vec<_> = notes.bus_ids.iter().flatten().copied().collect();
+
+    let remainders: Vec<_> = bus_ids.iter().map(|id| id - (arrival_time % id)).collect();
+    let a = remainders
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, x)| *x)
+        .unwrap();
+
+    let bus_id = bus_ids[a.0];
+    let remainder = a.1;
+    log!("bus: {}, waiting time: {}", bus_id, remainder);
+    bus_id * remainder
+}
+
+pub fn extended_euclidean(a: i128, b: i128) -> (i128, i128) {
+    // return quotients (s, t) such that s*a + t*b = gcd(a, b)
+    if a < b {
+        let (s, t) = extended_euclidean(b, a);
+        return (t, s);
+    }
+
+    let mut r0 = a;
+    let mut r1 = b;
+
+    let mut s0 = 1;
+    let mut s1 = 0;
+
+    let mut t0 = 0;
+    let mut t1 = 1;
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        let r = r0 % r1;
+        let s = s0 - q * s1;
+        let t = t0 - q * t1;
+
+        r0 = r1;
+        s0 = s1;
+        t0 = t1;
+        r1 = r;
+        s1 = s;
+        t1 = t;
+    }
+
+    (s0, t0)
+}
+
+pub fn day13b(notes: &Notes) -> i128 {
+    let bus_ids: Vec<_> = notes
+        .bus_ids
+        .iter()
+        .enumerate()
+        .filter_map(|(t, id)| id.map(|id| (t, id as i128)))
+        .map(|(t, bus_id)| ((bus_id - (t as i128)).rem_euclid(bus_id), bus_id))
+        .collect();
+
+    if bus_ids.len() == 1 {
+        // Only one bus, so the earliest time is its ID.
+        return bus_ids[0].1;
+    }
+
+    // Chinese remainder theorem
+    // use the construction as shown on the Wikipedia page.
+
+    let (a, b) = (bus_ids[0].0, bus_ids[1].0);
+    let (s, t) = extended_euclidean(bus_ids[0].1, bus_ids[1].1);
+
+    let mut x = b * s * bus_ids[0].1 + a * t * bus_ids[1].1;
+    let mut prod = bus_ids[0].1 * bus_ids[1].1;
+
+    x = x.rem_euclid(prod);
+
+    log!("s: {}, t: {}, {} {}", s, t, x, prod);
+
+    for (remainder, bus_id) in bus_ids[2..].iter() {
+        let (s, t) = extended_euclidean(prod, *bus_id);
+        x = remainder * s * prod + (x % prod) * t * bus_id;
+
+        prod *= bus_id;
+        x = x.rem_euclid(prod);
+        log!("s: {}, t: {}, {} {} {}", s, t, bus_id, x, prod);
+        trace!(
+            "crt",
+            bus = *bus_id,
+            remainder = *remainder,
+            x = x,
+            product = prod
+        );
+    }
+
+    x
+}
+
+/// Makes up a schedule where the buses have different prime ids, so that
+/// there's always a time that works for part two. The first place always has
+/// a bus, and there are only as many buses as keep that time reasonable.
+fn generate_bus_ids(rng: &mut Rng, size: usize) -> Vec<Option<u64>> {
+    let mut primes: Vec<u64> = (7..200u64)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect();
+    rng.shuffle(&mut primes);
+
+    let mut places: Vec<usize> = (1..size).collect();
+    rng.shuffle(&mut places);
+    places.insert(0, 0);
+
+    let mut ids = vec![None; size.max(1)];
+    let mut product: u64 = 1;
+    for (place, id) in places.into_iter().zip(primes) {
+        if product * id > 1_000_000_000_000_000 {
+            break;
+        }
+        product *= id;
+        ids[place] = Some(id);
+    }
+
+    ids
+}
+
+const EXAMPLE: &str = "\
+939
+7,13,x,x,59,x,31,19
+";
+
+const EXAMPLE_2: &str = "\
+939
+17,x,13,19
+";
+
+const EXAMPLE_3: &str = "\
+939
+67,7,59,61
+";
+
+const EXAMPLE_4: &str = "\
+939
+67,x,7,59,61
+";
+
+const EXAMPLE_5: &str = "\
+939
+67,7,x,59,61
+";
+
+const EXAMPLE_6: &str = "\
+939
+1789,37,47,1889
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day13>(Part::BOTH);
+    }
+}
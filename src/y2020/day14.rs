@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::error::Error;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+
+pub struct Day14;
+
+pub enum Command {
+    Mask(String),
+    Write(u64, u64),
+}
+
+impl Solver for Day14 {
+    const DAY: u32 = 14;
+    const TITLE: &'static str = "Docking Data";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("165"),
+            part_two: None,
+            params: &[],
+        },
+        Example {
+            input: FLOATING_EXAMPLE,
+            part_one: None,
+            part_two: Some("208"),
+            params: &[],
+        },
+    ];
+
+    /// Lines of the initialization program.
+    const INPUT_SIZE: usize = 550;
+
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Vec<Command>, Error> {
+        let re = Regex::new(r"^mem\[(.*)\] = (.*)$").unwrap();
+
+        parse::lines(input)
+            .map(|line| {
+                if let Some(mask) = line.text.trim().strip_prefix("mask = ") {
+                    if mask.len() != 36 || mask.contains(|c| !matches!(c, '0' | '1' | 'X')) {
+                        return Err(line.error(mask, "expected 36 of `0`, `1` or `X`"));
+                    }
+                    Ok(Command::Mask(mask.to_string()))
+                } else {
+                    let caps = match re.captures(line.text.trim()) {
+                        Some(caps) => caps,
+                        None => {
+                            let message = "expected `mask = <mask>` or `mem[<address>] = <value>`";
+                            return Err(line.error(line.text, message));
+                        }
+                    };
+                    let addr = line.parse::<u64>(caps.get(1).unwrap().as_str())?;
+                    let value = line.parse::<u64>(caps.get(2).unwrap().as_str())?;
+                    Ok(Command::Write(addr, value))
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(program: &Vec<Command>, _params: &Params) -> Result<Answer, Error> {
+        Ok(day14a(program).into())
+    }
+
+    fn part_two(program: &Vec<Command>, _params: &Params) -> Result<Answer, Error> {
+        Ok(day14b(program).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        let mut n_lines = 0;
+        while n_lines < size {
+            // at most 9 floating bits, so part two writes at most 512 addresses
+            let n_floating = rng.below(10);
+            let mut mask: Vec<char> = (0..36)
+                .map(|idx| match idx < n_floating {
+                    true => 'X',
+                    false => *rng.choose(&['0', '1']),
+                })
+                .collect();
+            rng.shuffle(&mut mask);
+            input.push_str(&format!(
+                "mask = {}\n",
+                mask.into_iter().collect::<String>()
+            ));
+
+            let n_writes = rng.range(1..=6) as usize;
+            for _ in 0..n_writes {
+                let address = rng.below(1 << 16);
+                let value = rng.next_u64() >> 28;
+                input.push_str(&format!("mem[{}] = {}\n", address, value));
+            }
+            n_lines += 1 + n_writes;
+        }
+
+        Some(input)
+    }
+}
+
+pub fn day14a(program: &[Command]) -> u64 {
+    let mut memory: HashMap<u64, u64> = HashMap::new();
+
+    let mut and_mask: u64 = 0;
+    let mut set_mask: u64 = 0;
+
+    for command in program {
+        match command {
+            Command::Mask(mask_str) => {
+                let and_mask_str: String = mask_str
+                    .chars()
+                    .map(|c| if c == 'X' { '1' } else { '0' })
+                    .collect();
+                and_mask = u64::from_str_radix(&and_mask_str, 2).unwrap();
+
+                let set_mask_str: String = mask_str
+                    .chars()
+                    .map(|c| if c == 'X' { '0' } else { c })
+                    .collect();
+                set_mask = u64::from_str_radix(&set_mask_str, 2).unwrap();
+            }
+            Command::Write(addr, value) => {
+                memory.insert(*addr, (value & and_mask) | set_mask);
+            }
+        }
+    }
+
+    memory.values().sum()
+}
+
+/// given an integer and a list of positions,
+/// fill in a bitmask at those positions with the binary
+/// representation of the integer, and return the final integer
+///
+/// E.g. make_mask(7, [0, 5, 6]) gives 0b1100001.
+/// E.g. make_mask(7, [0, 1, 3]) gives 0b0001011.
+pub fn make_mask(x: u64, positions: &[usize]) -> u64 {
+    let mut mask = ['0'; 36];
+
+    let bin_rep: String = format!("{:036b}", x).chars().rev().collect();
+    for (idx, c) in bin_rep.chars().enumerate() {
+        if idx >= positions.len() {
+            break;
+        }
+        mask[positions[idx]] = c;
+    }
+
+    let mask_str: String = mask.iter().rev().collect();
+    u64::from_str_radix(&mask_str, 2).unwrap()
+}
+
+pub fn day14b(program: &[Command]) -> u64 {
+    let mut memory: HashMap<u64, u64> = HashMap::new();
+
+    let mut or_mask: u64 = 0;
+    let mut and_mask: u64 = 0;
+    let mut float_positions = Vec::new();
+
+    for command in program {
+        match command {
+            Command::Mask(mask_str) => {
+                let or_mask_str: String = mask_str
+                    .chars()
+                    .map(|c| if c == 'X' { '0' } else { c })
+                    .collect();
+                or_mask = u64::from_str_radix(&or_mask_str, 2).unwrap();
+
+                let and_mask_str: String = mask_str
+                    .chars()
+                    .map(|c| if c == 'X' { '0' } else { '1' })
+                    .collect();
+                and_mask = u64::from_str_radix(&and_mask_str, 2).unwrap();
+
+                float_positions = mask_str
+                    .chars()
+                    .rev()
+                    .enumerate()
+                    .filter(|(_, c)| *c == 'X')
+                    .map(|(idx, _)| idx)
+                    .collect();
+            }
+            Command::Write(addr, value) => {
+                let float_max = 2u64.pow(float_positions.len() as u32);
+
+                // brute force: go through all possibilities
+                for i in 0..float_max {
+                    let mask = make_mask(i, &float_positions);
+
+                    let new_addr = ((addr & and_mask) | or_mask) | mask;
+                    memory.insert(new_addr, *value);
+                }
+            }
+        }
+    }
+
+    memory.values().sum()
+}
+
+const EXAMPLE: &str = "\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0
+";
+
+const FLOATING_EXAMPLE: &str = "\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day14>(Part::BOTH);
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use crate::checkpoint::{Checkpoint, Snapshot};
+use crate::error::Error;
+use crate::params::{Param, Params};
+use crate::parse;
+use crate::progress::Progress;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u32 = 15;
+    const TITLE: &'static str = "Rambunctious Recitation";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "0,3,6\n",
+            part_one: Some("436"),
+            part_two: Some("175594"),
+            params: &[],
+        },
+        Example {
+            input: "1,3,2\n",
+            part_one: Some("1"),
+            part_two: Some("2578"),
+            params: &[],
+        },
+        Example {
+            input: "2,1,3\n",
+            part_one: Some("10"),
+            part_two: Some("3544142"),
+            params: &[],
+        },
+        Example {
+            input: "1,2,3\n",
+            part_one: Some("27"),
+            part_two: Some("261214"),
+            params: &[],
+        },
+        Example {
+            input: "2,3,1\n",
+            part_one: Some("78"),
+            part_two: Some("6895259"),
+            params: &[],
+        },
+        Example {
+            input: "3,2,1\n",
+            part_one: Some("438"),
+            part_two: Some("18"),
+            params: &[],
+        },
+        Example {
+            input: "3,1,2\n",
+            part_one: Some("1836"),
+            part_two: Some("362"),
+            params: &[],
+        },
+    ];
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "turns",
+            part: Some(Part::One),
+            default: "2020",
+            help: "which number spoken is the answer",
+        },
+        Param {
+            name: "turns",
+            part: Some(Part::Two),
+            default: "30000000",
+            help: "which number spoken is the answer",
+        },
+    ];
+
+    /// Starting numbers, up to 1000.
+    const INPUT_SIZE: usize = 7;
+
+    /// A repeated starting number counts as said before, and short games stop
+    /// among the starting numbers.
+    const VERSION: u32 = 2;
+
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        let line = match parse::lines(input).next() {
+            Some(line) => line,
+            None => return Err(parse::end_of_input(input, "expected the starting numbers")),
+        };
+
+        let numbers = line
+            .text
+            .trim()
+            .split(',')
+            .map(|x| line.parse::<usize>(x))
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.is_empty() {
+            return Err(line.error(line.text, "expected the starting numbers"));
+        }
+
+        Ok(numbers)
+    }
+
+    fn part_one(numbers: &Vec<usize>, params: &Params) -> Result<Answer, Error> {
+        play(numbers, turns(params)?).map(Answer::from)
+    }
+
+    fn part_two(numbers: &Vec<usize>, params: &Params) -> Result<Answer, Error> {
+        play(numbers, turns(params)?).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        // the starting numbers are all different
+        let size = size.min(1000);
+        let mut numbers: Vec<_> = (0..3 * size).collect();
+        rng.shuffle(&mut numbers);
+        let numbers: Vec<_> = numbers[..size].iter().map(|n| n.to_string()).collect();
+        Some(format!("{}\n", numbers.join(",")))
+    }
+}
+
+/// The number of turns to play, which has to be at least one.
+fn turns(params: &Params) -> Result<usize, Error> {
+    match params.get("turns")? {
+        0 => Err(Error::InvalidParam(
+            "need at least 1 turn, not 0".to_string(),
+        )),
+        turns => Ok(turns),
+    }
+}
+
+/// The game partway through, just before `turn` is taken.
+#[derive(Debug, PartialEq)]
+struct Game {
+    /// The last turn each number was said on, other than `next_num`.
+    last_said: HashMap<usize, usize>,
+    /// The number being said on `turn`.
+    next_num: usize,
+    turn: usize,
+}
+
+impl Game {
+    /// The game as the last of the (non-empty) starting numbers is said.
+    fn new(numbers: &[usize]) -> Game {
+        // just use brute force?
+        let (last, earlier) = numbers.split_last().unwrap();
+        let mut last_said = HashMap::new();
+
+        for (idx, n) in earlier.iter().enumerate() {
+            last_said.insert(*n, idx + 1);
+        }
+
+        Game {
+            last_said,
+            next_num: *last,
+            turn: numbers.len(),
+        }
+    }
+
+    fn take_turn(&mut self) {
+        let idx = self.turn;
+        match self.last_said.get(&self.next_num) {
+            None => {
+                self.last_said.insert(self.next_num, idx);
+                self.next_num = 0;
+            }
+            Some(k) => {
+                let diff = idx - k;
+                self.last_said.insert(self.next_num, idx);
+                self.next_num = diff;
+            }
+        }
+        self.turn += 1;
+    }
+
+    /// Whether a loaded game could be one playing `limit` turns, checked
+    /// before trusting it with any.
+    fn fits(&self, numbers: &[usize], limit: usize) -> bool {
+        (numbers.len()..=limit).contains(&self.turn)
+            && self
+                .last_said
+                .values()
+                .all(|turn| (1..self.turn).contains(turn))
+    }
+}
+
+impl Snapshot for Game {
+    fn to_words(&self) -> Vec<u64> {
+        let mut words = vec![self.next_num as u64, self.turn as u64];
+        for (n, turn) in &self.last_said {
+            words.extend_from_slice(&[*n as u64, *turn as u64]);
+        }
+        words
+    }
+
+    fn from_words(words: &[u64]) -> Option<Game> {
+        if words.len() < 2 || !words.len().is_multiple_of(2) {
+            return None;
+        }
+        Some(Game {
+            last_said: words[2..]
+                .chunks(2)
+                .map(|pair| (pair[0] as usize, pair[1] as usize))
+                .collect(),
+            next_num: words[0] as usize,
+            turn: words[1] as usize,
+        })
+    }
+}
+
+/// Returns the `limit`th number spoken, counting from 1, unless the game is
+/// cancelled first.
+pub fn play(numbers: &[usize], limit: usize) -> Result<usize, Error> {
+    if limit <= numbers.len() {
+        return Ok(numbers[limit - 1]);
+    }
+
+    let mut checkpoint = Checkpoint::new("day15", format!("{:?} turns={}", numbers, limit));
+    let mut game = checkpoint
+        .as_ref()
+        .and_then(|checkpoint| checkpoint.load(|game: &Game| game.fits(numbers, limit)))
+        .unwrap_or_else(|| Game::new(numbers));
+
+    let mut progress = Progress::new("turn", game.turn, limit);
+    while game.turn < limit {
+        game.take_turn();
+        progress.update(game.turn)?;
+        if let Some(checkpoint) = &mut checkpoint {
+            if checkpoint.due(game.turn) {
+                checkpoint.save(&game);
+            }
+        }
+    }
+
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish();
+    }
+    Ok(game.next_num)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::Cancel;
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day15>(&[Part::One]);
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn examples_part_two() {
+        check_examples::<Day15>(&[Part::Two]);
+    }
+
+    #[test]
+    fn resumes_from_snapshot() {
+        let mut game = Game::new(&[0, 3, 6]);
+        while game.turn < 1000 {
+            game.take_turn();
+        }
+
+        let mut resumed = Game::from_words(&game.to_words()).unwrap();
+        assert_eq!(resumed, game);
+        while resumed.turn < 2020 {
+            resumed.take_turn();
+        }
+        assert_eq!(resumed.next_num, 436);
+    }
+
+    #[test]
+    fn rejects_corrupt_snapshots() {
+        let game = Game::new(&[0, 3, 6]);
+        assert!(game.fits(&[0, 3, 6], 2020));
+        // past the end of the game
+        assert!(!game.fits(&[0, 3, 6], 2));
+
+        let mut words = game.to_words();
+        words[1] = 0;
+        assert!(!Game::from_words(&words).unwrap().fits(&[0, 3, 6], 2020));
+
+        // a number said on a turn that hasn't happened yet
+        let mut words = game.to_words();
+        words.extend_from_slice(&[7, 10]);
+        assert!(!Game::from_words(&words).unwrap().fits(&[0, 3, 6], 2020));
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let cancel = Cancel::new();
+        cancel.cancel();
+        assert_eq!(
+            cancel.run(|| play(&[0, 3, 6], 30_000_000)),
+            Err(Error::Cancelled)
+        );
+    }
+
+    #[test]
+    fn short_games() {
+        assert_eq!(play(&[0, 3, 6], 1), Ok(0));
+        assert_eq!(play(&[0, 3, 6], 2), Ok(3));
+        assert_eq!(play(&[0, 3, 6], 3), Ok(6));
+        assert_eq!(play(&[0, 3, 6], 4), Ok(0));
+        // a repeated starting number has been said before
+        assert_eq!(play(&[1, 1], 3), Ok(1));
+
+        let mut params = Params::new();
+        params.set("turns", "0");
+        assert!(matches!(
+            Day15::solve_with("0,3,6\n", Part::One, &params),
+            Err(Error::InvalidParam(_))
+        ));
+    }
+}
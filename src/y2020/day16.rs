@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use regex::Regex;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day16;
+
+#[derive(Clone)]
+pub struct Field {
+    pub range1: Range<i32>,
+    pub range2: Range<i32>,
+}
+
+/// The ticket rules, our ticket and the nearby tickets.
+pub struct Notes {
+    pub fields: HashMap<String, Field>,
+    pub ticket: Vec<i32>,
+    pub nearby_tickets: Vec<Vec<i32>>,
+}
+
+impl Solver for Day16 {
+    const DAY: u32 = 16;
+    const TITLE: &'static str = "Ticket Translation";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("71"),
+        part_two: None,
+        params: &[],
+    }];
+
+    /// Nearby tickets.
+    const INPUT_SIZE: usize = 240;
+
+    type Input = Notes;
+
+    fn parse(input: &str) -> Result<Notes, Error> {
+        let mut sections = parse::sections(input);
+
+        let fields = sections.unnamed("fields")?.records(parse_field)?;
+        let fields: HashMap<_, _> = fields.into_iter().collect();
+
+        let our_ticket = sections.named("your ticket:")?;
+        let mut tickets = our_ticket.records(|line| parse_ticket(line, fields.len()))?;
+        if tickets.len() != 1 {
+            let header = our_ticket.header.unwrap();
+            let error = header.error(header.text, "expected exactly one ticket");
+            return Err(our_ticket.in_section(error));
+        }
+        let ticket = tickets.remove(0);
+
+        let nearby_tickets = sections
+            .named("nearby tickets:")?
+            .records(|line| parse_ticket(line, fields.len()))?;
+        sections.finish()?;
+
+        Ok(Notes {
+            fields,
+            ticket,
+            nearby_tickets,
+        })
+    }
+
+    fn part_one(notes: &Notes, _params: &Params) -> Result<Answer, Error> {
+        Ok(day16a(notes).into())
+    }
+
+    fn part_two(notes: &Notes, _params: &Params) -> Result<Answer, Error> {
+        day16b(notes).map(Answer::from)
+    }
+
+    fn details(notes: &Notes, part: Part, _params: &Params) -> Option<Json> {
+        if part != Part::Two {
+            return None;
+        }
+
+        // the position of each field, in order
+        let mut positions: Vec<_> = field_positions(notes).ok()?.into_iter().collect();
+        positions.sort();
+        let fields = positions
+            .into_iter()
+            .map(|(pos, field_name)| (field_name, pos.into()));
+        Some(Json::object(vec![(
+            "field_positions",
+            Json::object(fields),
+        )]))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_notes(rng, size))
+    }
+}
+
+/// checks if a number is within the valid ranges for the field.
+pub fn valid_for_field(field: &Field, x: &i32) -> bool {
+    field.range1.contains(x) || field.range2.contains(x)
+}
+
+/// returns None if no fields are invalid,
+/// else returns the sum of the invalid fields
+pub fn invalid_sum(fields: &HashMap<String, Field>, ticket: &[i32]) -> Option<i32> {
+    let mut sum = 0;
+    let mut valid_ticket = true;
+
+    for v in ticket {
+        let mut valid_value = false;
+        for field in fields.values() {
+            if valid_for_field(field, v) {
+                valid_value = true;
+                break;
+            }
+        }
+
+        if !valid_value {
+            valid_ticket = false;
+            sum += v;
+        }
+    }
+
+    if valid_ticket {
+        None
+    } else {
+        Some(sum)
+    }
+}
+
+fn parse_field(line: Line) -> Result<(String, Field), Error> {
+    let field_re = Regex::new(r"^(.*): ([0-9]+)-([0-9]+) or ([0-9]+)-([0-9]+)$").unwrap();
+
+    let caps = match field_re.captures(line.text.trim()) {
+        Some(caps) => caps,
+        None => return Err(line.error(line.text, "expected `<field>: <a>-<b> or <c>-<d>`")),
+    };
+    let name = caps.get(1).unwrap().as_str().to_string();
+    let start1 = line.parse::<i32>(caps.get(2).unwrap().as_str())?;
+    let end1 = line.parse::<i32>(caps.get(3).unwrap().as_str())?;
+    let start2 = line.parse::<i32>(caps.get(4).unwrap().as_str())?;
+    let end2 = line.parse::<i32>(caps.get(5).unwrap().as_str())?;
+
+    let field = Field {
+        range1: start1..(end1 + 1),
+        range2: start2..(end2 + 1),
+    };
+    Ok((name, field))
+}
+
+fn parse_ticket(line: Line, n_fields: usize) -> Result<Vec<i32>, Error> {
+    let ticket = line
+        .text
+        .trim()
+        .split(',')
+        .map(|v| line.parse::<i32>(v))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if ticket.len() != n_fields {
+        let message = format!("expected {} values, one for each field", n_fields);
+        return Err(line.error(line.text, message));
+    }
+
+    Ok(ticket)
+}
+
+pub fn day16a(notes: &Notes) -> i32 {
+    notes
+        .nearby_tickets
+        .iter()
+        .map(|ticket| invalid_sum(&notes.fields, ticket).unwrap_or(0))
+        .sum()
+}
+
+/// given all tickets, fields remaining to be assigned,
+/// and currently assigned positions,
+/// find a new position.
+fn get_position(
+    tickets: &[Vec<i32>],
+    max_position: usize,
+    fields: &HashMap<String, Field>,
+    assigned_positions: &HashMap<usize, String>,
+) -> Result<(String, usize), Error> {
+    let mut possible_positions: HashMap<&String, Vec<usize>> = HashMap::new();
+
+    for (name, field) in fields {
+        for pos in 0..max_position {
+            if assigned_positions.contains_key(&pos) {
+                continue;
+            }
+
+            let mut valid_pos = true;
+            for ticket in tickets {
+                if !valid_for_field(field, &ticket[pos]) {
+                    valid_pos = false;
+                    break;
+                }
+            }
+
+            if valid_pos {
+                possible_positions.entry(name).or_default().push(pos);
+            }
+        }
+
+        match possible_positions.get(name) {
+            None => {
+                return Err(Error::NoSolution(format!(
+                    "couldn't find position for {}",
+                    name
+                )))
+            }
+            Some(set) => {
+                if set.len() == 1 {
+                    log!("found position {} for {}", set[0], name);
+                    return Ok((name.clone(), set[0]));
+                }
+            }
+        }
+    }
+
+    Err(Error::NoSolution(
+        "no field with only 1 possible position!".to_string(),
+    ))
+}
+
+/// Works out which position on the tickets each field is in.
+pub fn field_positions(notes: &Notes) -> Result<HashMap<usize, String>, Error> {
+    let fields = &notes.fields;
+    let max_positions = notes.ticket.len();
+
+    log!("ticket has {} positions", max_positions);
+
+    let mut discarded_tickets = 0;
+    let mut valid_tickets = Vec::new();
+    for ticket in &notes.nearby_tickets {
+        match invalid_sum(fields, ticket) {
+            None => valid_tickets.push(ticket.clone()),
+            Some(_) => discarded_tickets += 1,
+        };
+    }
+
+    log!("discarded {} tickets", discarded_tickets);
+    log!("{} valid tickets left", valid_tickets.len());
+
+    // see if this works: find a field that has only one possible position,
+    // then set it, and keep looping.
+
+    let mut field_positions = HashMap::new();
+    let mut fields_to_assign = fields.clone();
+    while field_positions.len() < max_positions {
+        let new_position = get_position(
+            &valid_tickets,
+            max_positions,
+            &fields_to_assign,
+            &field_positions,
+        )?;
+        if fields_to_assign.remove(&new_position.0).is_none() {
+            panic!("position assigned to non-existent field {}", new_position.0);
+        }
+        field_positions.insert(new_position.1, new_position.0);
+    }
+
+    Ok(field_positions)
+}
+
+pub fn day16b(notes: &Notes) -> Result<i128, Error> {
+    let ticket = &notes.ticket;
+    let field_positions = field_positions(notes)?;
+
+    log!("------");
+    let mut prod = 1;
+    for (pos, field_name) in field_positions.iter() {
+        if field_name.starts_with("departure") {
+            log!(
+                "{}: {} (our ticket value: {})",
+                field_name,
+                pos,
+                ticket[*pos]
+            );
+            prod *= ticket[*pos] as i128;
+        }
+    }
+    Ok(prod)
+}
+
+const FIELD_NAMES: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Makes up notes where the fields can be worked out one at a time.
+///
+/// The fields are put in a random order, and the values of the `k`th field
+/// come from the `k`th of a series of separate intervals. The `k`th field's
+/// ranges cover the first `k` intervals and none of the others, so it can go
+/// in the position of any of the first `k` fields, and there's always one
+/// field left with only one possible position.
+fn generate_notes(rng: &mut Rng, size: usize) -> String {
+    let n_fields = FIELD_NAMES.len();
+
+    // the intervals, with gaps between them that no ticket uses
+    let mut intervals = Vec::new();
+    let mut start = rng.range(25..=30);
+    for _ in 0..n_fields {
+        let end = start + rng.range(10..=40);
+        intervals.push((start, end));
+        start = end + 1 + rng.range(1..=10);
+    }
+    let upper_limit = start + 10;
+    let next_start = |k: usize| {
+        intervals
+            .get(k + 1)
+            .map_or(upper_limit, |(start, _)| *start)
+    };
+
+    let mut ranks: Vec<usize> = (0..n_fields).collect();
+    rng.shuffle(&mut ranks);
+    let mut positions: Vec<usize> = (0..n_fields).collect();
+    rng.shuffle(&mut positions);
+
+    let mut input = String::new();
+    for (name, rank) in FIELD_NAMES.iter().zip(&ranks) {
+        // split the ranges somewhere in the gap after one of the intervals
+        let split = rng.below(rank + 1);
+        let cut = rng.range(intervals[split].1 + 1..=next_start(split) - 1);
+        let low = intervals[0].0 - rng.range(0..=5);
+        let (start2, end2) = match split == *rank {
+            true => (cut, cut),
+            false => {
+                let start2 = rng.range(cut..=intervals[split + 1].0);
+                (
+                    start2,
+                    rng.range(intervals[*rank].1..=next_start(*rank) - 1),
+                )
+            }
+        };
+        input.push_str(&format!(
+            "{}: {}-{} or {}-{}\n",
+            name,
+            low,
+            cut - 1,
+            start2,
+            end2
+        ));
+    }
+
+    let ticket = |rng: &mut Rng, valid: bool| {
+        let mut values = vec![0; n_fields];
+        for (rank, position) in positions.iter().enumerate() {
+            let (start, end) = intervals[rank];
+            values[*position] = rng.range(start..=end);
+        }
+        if !valid {
+            let invalid = match rng.chance(0.5) {
+                true => rng.range(0..=19),
+                false => rng.range(upper_limit..=upper_limit + 50),
+            };
+            values[rng.below(n_fields)] = invalid;
+        }
+
+        let values: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+        values.join(",") + "\n"
+    };
+
+    input.push_str("\nyour ticket:\n");
+    input.push_str(&ticket(rng, true));
+    input.push_str("\nnearby tickets:\n");
+    for idx in 0..size {
+        // there must be at least one valid ticket to rule positions out
+        let valid = idx == 0 || !rng.chance(0.25);
+        input.push_str(&ticket(rng, valid));
+    }
+
+    input
+}
+
+const EXAMPLE: &str = "\
+class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day16>(Part::BOTH);
+    }
+
+    #[test]
+    fn field_positions() {
+        // the part two example has no departure fields, so rename the ones
+        // that end up in positions 1 and 2.
+        let input = "\
+departure class: 0-1 or 4-19
+row: 0-5 or 8-19
+departure seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9
+";
+        let notes = Day16::parse(input).unwrap();
+        assert_eq!(day16b(&notes), Ok(12 * 13));
+    }
+}
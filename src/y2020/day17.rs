@@ -0,0 +1,106 @@
+use crate::automaton::{Automaton, Lattice, Rule};
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::params::{Param, Params};
+use crate::render::{self, Frame};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u32 = 17;
+    const TITLE: &'static str = "Conway Cubes";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("112"),
+        part_two: Some("848"),
+        params: &[],
+    }];
+    const PARAMS: &'static [Param] = &[Param {
+        name: "cycles",
+        part: None,
+        default: "6",
+        help: "how many cycles to run the cubes for",
+    }];
+
+    /// The width and height of the slice.
+    const INPUT_SIZE: usize = 8;
+
+    /// The slice of the pocket dimension that starts with active cubes.
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Grid<bool>, Error> {
+        read_state(input)
+    }
+
+    fn part_one(slice: &Grid<bool>, params: &Params) -> Result<Answer, Error> {
+        Ok(run_cycles::<3>(slice, params.get("cycles")?).into())
+    }
+
+    fn part_two(slice: &Grid<bool>, params: &Params) -> Result<Answer, Error> {
+        Ok(run_cycles::<4>(slice, params.get("cycles")?).into())
+    }
+
+    fn frames(slice: &Grid<bool>, part: Part, params: &Params) -> Result<Vec<Frame>, Error> {
+        let n_cycles = Some(params.get("cycles")?);
+        Ok(match part {
+            Part::One => render::lattice_frames(&cubes::<3>(slice).history(n_cycles)),
+            Part::Two => render::lattice_frames(&cubes::<4>(slice).history(n_cycles)),
+        })
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let rows = (0..size).map(|_| (0..size).map(|_| rng.chance(0.5)).collect());
+        let slice = Grid::from_rows(rows.collect());
+        Some(slice.render(|active| if *active { '#' } else { '.' }))
+    }
+}
+
+fn read_state(input: &str) -> Result<Grid<bool>, Error> {
+    let cell = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    Grid::parse(input, cell, "expected `#` or `.`")
+}
+
+/// The cubes in `N` dimensions, starting from the active cubes of a slice.
+fn cubes<const N: usize>(slice: &Grid<bool>) -> Automaton<Lattice<N>> {
+    let active = slice
+        .iter()
+        .filter(|(_, active)| **active)
+        .map(|((y, x), _)| {
+            let mut coord = [0; N];
+            coord[0] = x as i64;
+            coord[1] = y as i64;
+            coord
+        });
+
+    Automaton::new(Lattice::<N>::new(), Rule::new(3..=3, 2..=3), active)
+}
+
+/// Runs the cubes in `N` dimensions, and counts the active cubes at the end.
+pub fn run_cycles<const N: usize>(slice: &Grid<bool>, n_cycles: usize) -> usize {
+    let mut cubes = cubes::<N>(slice);
+    cubes.run(n_cycles);
+    cubes.population()
+}
+
+const EXAMPLE: &str = "\
+.#.
+..#
+###
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day17>(Part::BOTH);
+    }
+}
@@ -0,0 +1,402 @@
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+use crate::trace;
+
+pub struct Day18;
+
+/*
+For part 2 (with equal precedence, a term is just a part, and the
+expression can add terms as well):
+
+with help from http://math.hws.edu/javanotes/c9/s5.html
+
+expression =>
+| term
+| term multiply term
+
+term =>
+| part
+| part add part
+
+part =>
+| number
+| leftparen expression rightparen
+*/
+
+impl Solver for Day18 {
+    const DAY: u32 = 18;
+    const TITLE: &'static str = "Operation Order";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("26335"),
+        part_two: Some("693891"),
+        params: &[],
+    }];
+
+    /// Expressions on the homework.
+    const INPUT_SIZE: usize = 375;
+
+    type Input = Vec<Vec<Token>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Token>>, Error> {
+        parse::lines(input).map(parse_expression).collect()
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, parse_expression)
+    }
+
+    fn part_one(expressions: &Vec<Vec<Token>>, _params: &Params) -> Result<Answer, Error> {
+        let eval = |tokens: &[Token]| eval_expression(tokens, Precedence::Equal);
+        Ok(sum_expressions(expressions, eval).into())
+    }
+
+    fn part_two(expressions: &Vec<Vec<Token>>, _params: &Params) -> Result<Answer, Error> {
+        let eval = |tokens: &[Token]| eval_expression2(tokens, Precedence::AddFirst);
+        Ok(sum_expressions(expressions, eval).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let lines = (0..size).map(|_| {
+            let n_digits = rng.range(2..=12) as usize;
+            generate_expression(rng, n_digits) + "\n"
+        });
+        Some(lines.collect())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Token {
+    Number(i64),
+    Add,
+    Multiply,
+    LeftParen,
+    RightParen,
+}
+
+/// Splits the line into tokens, along with the text of each one.
+fn lex<'a>(line: &Line<'a>) -> Result<Vec<(&'a str, Token)>, Error> {
+    let text = line.text;
+
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, token)) = chars.next() {
+        if token.is_whitespace() {
+            continue;
+        }
+
+        let mut end = start + token.len_utf8();
+        let token = match token {
+            '0'..='9' => {
+                while let Some((idx, c)) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    end = idx + 1;
+                    chars.next();
+                }
+                Token::Number(line.parse::<i64>(&text[start..end])?)
+            }
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '+' => Token::Add,
+            '*' => Token::Multiply,
+            _ => return Err(line.error(&text[start..end], format!("unknown token `{}`", token))),
+        };
+        tokens.push((&text[start..end], token));
+    }
+
+    Ok(tokens)
+}
+
+/// Lexes an expression, and checks that it is well-formed, so that it can
+/// be evaluated either way.
+pub fn parse_expression(line: Line) -> Result<Vec<Token>, Error> {
+    let tokens = lex(&line)?;
+
+    let mut expect_number = true;
+    let mut open_parens = Vec::new();
+    for (text, token) in &tokens {
+        match (expect_number, token) {
+            (true, Token::Number(_)) => expect_number = false,
+            (true, Token::LeftParen) => open_parens.push(*text),
+            (true, _) => return Err(line.error(text, "expected a number or `(`")),
+            (false, Token::Add) | (false, Token::Multiply) => expect_number = true,
+            (false, Token::RightParen) => {
+                if open_parens.pop().is_none() {
+                    return Err(line.error(text, "unmatched `)`"));
+                }
+            }
+            (false, _) => return Err(line.error(text, "expected an operator or `)`")),
+        }
+    }
+
+    if expect_number {
+        return Err(match tokens.last() {
+            Some((text, _)) => line.error(text, "expected a number or `(` after this"),
+            None => line.error(line.text, "expected an expression"),
+        });
+    }
+    if let Some(text) = open_parens.pop() {
+        return Err(line.error(text, "unclosed `(`"));
+    }
+
+    Ok(tokens.into_iter().map(|(_, token)| token).collect())
+}
+
+/// How tightly `+` binds compared to `*`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precedence {
+    /// The same, so evaluate left to right (part one).
+    Equal,
+    /// Tighter, so do additions first (part two).
+    AddFirst,
+}
+
+impl Precedence {
+    /// Whether the operator after `op` should be applied first.
+    fn defers(self, op: Token, next: Option<Token>) -> bool {
+        matches!(
+            (self, op, next),
+            (Precedence::AddFirst, Token::Multiply, Some(Token::Add))
+        )
+    }
+}
+
+/// Reduces the top of the stack as far as it goes, given the next token.
+fn reduce(stack: &mut Vec<Token>, next: Option<Token>, precedence: Precedence) {
+    while stack.len() >= 3 {
+        // look at the last three
+        let third = stack[stack.len() - 1];
+        let second = stack[stack.len() - 2];
+        let first = stack[stack.len() - 3];
+
+        let reduced = match (first, second, third) {
+            (Token::LeftParen, Token::Number(_), Token::RightParen) => second,
+            (Token::Number(_), op, Token::Number(_)) if precedence.defers(op, next) => break,
+            (Token::Number(a), Token::Add, Token::Number(b)) => Token::Number(a + b),
+            (Token::Number(a), Token::Multiply, Token::Number(b)) => Token::Number(a * b),
+            _ => break,
+        };
+        trace!(
+            "reduce",
+            from = format!("{:?} {:?} {:?}", first, second, third),
+            to = format!("{:?}", reduced)
+        );
+        stack.truncate(stack.len() - 3);
+        stack.push(reduced);
+    }
+}
+
+/// evaluate expression with a shift-reduce parser
+pub fn eval_expression(tokens: &[Token], precedence: Precedence) -> i64 {
+    let mut stack = Vec::new();
+
+    for (idx, token) in tokens.iter().enumerate() {
+        stack.push(*token);
+        reduce(&mut stack, tokens.get(idx + 1).copied(), precedence);
+    }
+
+    assert_eq!(stack.len(), 1);
+
+    match stack.pop().unwrap() {
+        Token::Number(result) => result,
+        _ => panic!("result is not number"),
+    }
+}
+
+fn get_part(tokens: &[Token], pos: usize, precedence: Precedence) -> (i64, usize) {
+    match tokens[pos] {
+        Token::Number(n) => (n, pos + 1),
+        Token::LeftParen => {
+            let (n, next_pos) = get_expression(tokens, pos + 1, precedence);
+            match tokens[next_pos] {
+                Token::RightParen => (n, next_pos + 1),
+                _ => panic!("rightparen expected at {}", next_pos),
+            }
+        }
+        _ => panic!("unexpected token {:?} at {}", tokens[pos], pos),
+    }
+}
+
+fn get_term(tokens: &[Token], pos: usize, precedence: Precedence) -> (i64, usize) {
+    let (mut value, mut next_pos) = get_part(tokens, pos, precedence);
+
+    // with equal precedence, additions are left to get_expression
+    while next_pos < tokens.len() && precedence == Precedence::AddFirst {
+        match tokens[next_pos] {
+            Token::Add => {
+                let (other_value, pos) = get_part(tokens, next_pos + 1, precedence);
+                value += other_value;
+                next_pos = pos;
+            }
+            _ => break,
+        }
+    }
+    (value, next_pos)
+}
+
+fn get_expression(tokens: &[Token], pos: usize, precedence: Precedence) -> (i64, usize) {
+    let (mut value, mut next_pos) = get_term(tokens, pos, precedence);
+
+    while next_pos < tokens.len() {
+        match tokens[next_pos] {
+            Token::Multiply => {
+                let (other_term, pos) = get_term(tokens, next_pos + 1, precedence);
+                value *= other_term;
+                next_pos = pos;
+            }
+            Token::Add => {
+                let (other_term, pos) = get_term(tokens, next_pos + 1, precedence);
+                value += other_term;
+                next_pos = pos;
+            }
+            _ => break,
+        }
+    }
+    (value, next_pos)
+}
+
+fn rec_dec_parser(tokens: &[Token], precedence: Precedence) -> i64 {
+    let (result, pos) = get_expression(tokens, 0, precedence);
+    if pos < tokens.len() {
+        panic!("syntax error!");
+    }
+    result
+}
+
+/// evaluate expression using recursive descent (grammar in comment at top)
+pub fn eval_expression2(tokens: &[Token], precedence: Precedence) -> i64 {
+    rec_dec_parser(tokens, precedence)
+}
+
+fn sum_expressions(expressions: &[Vec<Token>], eval_fn: impl Fn(&[Token]) -> i64) -> i64 {
+    let mut sum = 0;
+    for expression in expressions {
+        let result = eval_fn(expression);
+        log!("Evaluated as: {}", result);
+        sum += result;
+    }
+
+    sum
+}
+
+/// Makes up an expression with the given number of single digits, so that
+/// the answer always fits (at most 9^12).
+fn generate_expression(rng: &mut Rng, n_digits: usize) -> String {
+    let mut expression = String::new();
+    let mut left = n_digits;
+    while left > 0 {
+        if !expression.is_empty() {
+            expression.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+
+        // the first operand can't take all the digits, or there'd be
+        // parentheses around the whole expression
+        let most = if expression.is_empty() {
+            left - 1
+        } else {
+            left
+        };
+        if most >= 2 && rng.chance(0.3) {
+            let inner = rng.range(2..=most.min(6) as i64) as usize;
+            expression.push_str(&format!("({})", generate_expression(rng, inner)));
+            left -= inner;
+        } else {
+            expression.push_str(&rng.range(1..=9).to_string());
+            left -= 1;
+        }
+    }
+
+    expression
+}
+
+const EXAMPLE: &str = "\
+2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)
+5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::check_agreement;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day18>(Part::BOTH);
+    }
+
+    #[test]
+    fn expressions() {
+        let cases = [
+            ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+            ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+            ("2 * 3 + (4 * 5)", 26, 46),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+            (
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                13632,
+                23340,
+            ),
+        ];
+        for (expression, first, second) in cases.iter() {
+            let tokens = parse_expression(Line {
+                number: 1,
+                text: expression,
+            })
+            .unwrap();
+            for eval in [eval_expression, eval_expression2].iter() {
+                let equal = eval(&tokens, Precedence::Equal);
+                let add_first = eval(&tokens, Precedence::AddFirst);
+                assert_eq!((equal, add_first), (*first, *second), "{}", expression);
+            }
+        }
+    }
+
+    #[test]
+    fn evaluators_agree() {
+        let tokens = |expression: &String| Day18::parse(expression).unwrap().remove(0);
+        for precedence in [Precedence::Equal, Precedence::AddFirst].iter() {
+            check_agreement(
+                500,
+                12,
+                generate_expression,
+                |expression| eval_expression(&tokens(expression), *precedence),
+                |expression| eval_expression2(&tokens(expression), *precedence),
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_expressions() {
+        let cases = [
+            ("1 + 2 & 3", 7, "unknown token `&`"),
+            ("1 + (2 * 3", 5, "unclosed `(`"),
+            ("1 + 2)", 6, "unmatched `)`"),
+            ("1 + * 2", 5, "expected a number or `(`"),
+            ("1 2", 3, "expected an operator or `)`"),
+            ("1 +", 3, "expected a number or `(` after this"),
+        ];
+        for (expression, column, message) in cases.iter() {
+            let line = Line {
+                number: 1,
+                text: expression,
+            };
+            match parse_expression(line) {
+                Err(Error::Parse(error)) => {
+                    assert_eq!(error.column, *column, "{}", expression);
+                    assert_eq!(error.message, *message, "{}", expression);
+                }
+                _ => panic!("expected a parse error for {}", expression),
+            }
+        }
+    }
+}
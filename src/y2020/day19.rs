@@ -0,0 +1,743 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line, Paragraph};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+use crate::trace;
+
+pub struct Day19;
+
+#[derive(Debug)]
+pub enum Rule {
+    Char(char),
+    Seq(Vec<u32>),
+    Choice(Vec<u32>, Vec<u32>),
+}
+
+pub type Rules = HashMap<u32, Rule>;
+
+/// The rules, and the received messages to check against them.
+pub struct Puzzle {
+    pub rules: Rules,
+    pub messages: Vec<String>,
+}
+
+impl Solver for Day19 {
+    const DAY: u32 = 19;
+    const TITLE: &'static str = "Monster Messages";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part_one: Some("2"),
+            part_two: None,
+            params: &[],
+        },
+        Example {
+            input: LOOPING_EXAMPLE,
+            part_one: Some("3"),
+            part_two: Some("12"),
+            params: &[],
+        },
+    ];
+
+    /// Received messages.
+    const INPUT_SIZE: usize = 400;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, Error> {
+        let mut sections = parse::sections(input);
+        let rules = sections.unnamed("rules")?;
+        let rules = read_rules(&rules).map_err(|error| rules.in_section(error))?;
+        let messages = sections
+            .flat_map(|messages| messages.lines)
+            .map(|line| line.text.trim().to_string())
+            .collect();
+
+        Ok(Puzzle { rules, messages })
+    }
+
+    fn part_one(puzzle: &Puzzle, _params: &Params) -> Result<Answer, Error> {
+        if !puzzle.rules.contains_key(&0) {
+            return Err(Error::NoSolution("there is no rule 0".to_string()));
+        }
+
+        Ok(count_valid(puzzle, is_valid).into())
+    }
+
+    fn part_two(puzzle: &Puzzle, _params: &Params) -> Result<Answer, Error> {
+        let rules = &puzzle.rules;
+        if !rules.contains_key(&42) || !rules.contains_key(&31) {
+            return Err(Error::NoSolution(
+                "the loops need rules 42 and 31".to_string(),
+            ));
+        }
+
+        let match_42 = get_possible_strings(rules, &42)?;
+        let match_31 = get_possible_strings(rules, &31)?;
+        let n_match = match_42.intersection(&match_31).count();
+        log!("strings that match 42 & 31: {}", n_match);
+        if n_match > 0 {
+            return Err(Error::NoSolution(
+                "rules 42 and 31 match some of the same strings".to_string(),
+            ));
+        }
+
+        Ok(count_valid(puzzle, is_valid_partb).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_puzzle(rng, size))
+    }
+}
+
+fn parse_rule(line: Line) -> Result<(u32, Rule), Error> {
+    let string = line.text.trim();
+    let char_re = Regex::new(r#"^([0-9]+): "([a-z])"$"#).unwrap();
+    let seq_re = Regex::new(r"^([0-9]+): ([0-9]+(?: [0-9]+)*)$").unwrap();
+    let choice_re =
+        Regex::new(r"^([0-9]+): ([0-9]+(?: [0-9]+)*) \| ([0-9]+(?: [0-9]+)*)$").unwrap();
+
+    match (
+        char_re.is_match(string),
+        seq_re.is_match(string),
+        choice_re.is_match(string),
+    ) {
+        (true, _, _) => {
+            let caps = char_re.captures(string).unwrap();
+            let id = line.parse::<u32>(caps.get(1).unwrap().as_str())?;
+            let c = caps[2].chars().next().unwrap();
+            Ok((id, Rule::Char(c)))
+        }
+        (_, true, _) => {
+            let caps = seq_re.captures(string).unwrap();
+            let id = line.parse::<u32>(caps.get(1).unwrap().as_str())?;
+            let values = parse_ids(&line, caps.get(2).unwrap().as_str())?;
+            Ok((id, Rule::Seq(values)))
+        }
+        (_, _, true) => {
+            let caps = choice_re.captures(string).unwrap();
+            let id = line.parse::<u32>(caps.get(1).unwrap().as_str())?;
+            let choice1 = parse_ids(&line, caps.get(2).unwrap().as_str())?;
+            let choice2 = parse_ids(&line, caps.get(3).unwrap().as_str())?;
+            Ok((id, Rule::Choice(choice1, choice2)))
+        }
+        _ => Err(line.error(
+            string,
+            r#"expected `<id>: "<letter>"` or `<id>: <ids>`, optionally `| <ids>`"#,
+        )),
+    }
+}
+
+fn parse_ids(line: &Line, ids: &str) -> Result<Vec<u32>, Error> {
+    ids.split_whitespace()
+        .map(|s| line.parse::<u32>(s))
+        .collect()
+}
+
+fn read_rules(paragraph: &Paragraph) -> Result<Rules, Error> {
+    let mut rules = HashMap::new();
+    let mut references = Vec::new();
+    for &line in &paragraph.lines {
+        let (id, rule) = parse_rule(line)?;
+        if rules.insert(id, rule).is_some() {
+            let (id_text, _) = line.split_once(":")?;
+            return Err(line.error(id_text.trim(), format!("rule {} is defined twice", id)));
+        }
+
+        // remember where other rules are used, to check that they exist
+        let (_, body) = line.split_once(":")?;
+        for id_text in body.split_whitespace() {
+            if let Ok(other) = id_text.parse::<u32>() {
+                references.push((line, id_text, id, other));
+            }
+        }
+    }
+    log!("read {} rules", rules.len());
+
+    for (line, id_text, _, other) in &references {
+        if !rules.contains_key(other) {
+            return Err(line.error(id_text, format!("rule {} is not defined", other)));
+        }
+    }
+
+    // matching a rule that loops back to itself would never end
+    if let Some(ids) = find_loop(&rules) {
+        let (first, last) = (ids[0], ids[ids.len() - 1]);
+        let (line, id_text, _, _) = references
+            .iter()
+            .find(|(_, _, id, other)| *id == last && *other == first)
+            .unwrap();
+        let path: Vec<_> = ids
+            .iter()
+            .chain([first].iter())
+            .map(u32::to_string)
+            .collect();
+        let message = format!(
+            "rule {} refers back to itself ({})",
+            first,
+            path.join(" -> ")
+        );
+        return Err(line.error(id_text, message));
+    }
+
+    Ok(rules)
+}
+
+/// The rules referred to by a rule, leaving out rules 8 and 11 referring to
+/// themselves, which is the one way they're allowed to loop (as in part two,
+/// where they're matched specially).
+fn references(rules: &Rules, id: u32) -> Vec<u32> {
+    let others = match &rules[&id] {
+        Rule::Char(_) => Vec::new(),
+        Rule::Seq(seq) => seq.clone(),
+        Rule::Choice(seq1, seq2) => seq1.iter().chain(seq2).copied().collect(),
+    };
+    others
+        .into_iter()
+        .filter(|other| !(*other == id && (id == 8 || id == 11)))
+        .collect()
+}
+
+/// A loop in the rules, as the ids going round it, if there is one.
+fn find_loop(rules: &Rules) -> Option<Vec<u32>> {
+    let mut ids: Vec<u32> = rules.keys().copied().collect();
+    ids.sort_unstable();
+
+    // a depth-first search, with a stack rather than recursion, so that a
+    // long chain of rules can't overflow it
+    let mut done = HashSet::new();
+    for root in ids {
+        if done.contains(&root) {
+            continue;
+        }
+        // the rules from the root, each with the references it has left
+        let mut path = vec![(root, references(rules, root))];
+        while let Some((id, left)) = path.last_mut() {
+            let id = *id;
+            let next = match left.pop() {
+                Some(next) => next,
+                None => {
+                    done.insert(id);
+                    path.pop();
+                    continue;
+                }
+            };
+            if let Some(start) = path.iter().position(|(on_path, _)| *on_path == next) {
+                return Some(path[start..].iter().map(|(id, _)| *id).collect());
+            }
+            if !done.contains(&next) {
+                path.push((next, references(rules, next)));
+            }
+        }
+    }
+
+    None
+}
+
+pub fn validate_rule(message: &str, rules: &Rules, rule_id: &u32, pos: usize) -> Option<usize> {
+    match &rules[rule_id] {
+        Rule::Char(c) => {
+            if message.chars().nth(pos) == Some(*c) {
+                Some(pos + 1)
+            } else {
+                None
+            }
+        }
+        Rule::Seq(rule_seq) => {
+            let mut next_pos = pos;
+            for rule in rule_seq {
+                match validate_rule(message, rules, rule, next_pos) {
+                    Some(p) => next_pos = p,
+                    None => return None,
+                }
+            }
+            Some(next_pos)
+        }
+        Rule::Choice(rule_seq1, rule_seq2) => {
+            let mut next_pos = pos;
+            let mut match_seq1 = true;
+            for rule in rule_seq1 {
+                match validate_rule(message, rules, rule, next_pos) {
+                    Some(p) => next_pos = p,
+                    None => {
+                        match_seq1 = false;
+                        break;
+                    }
+                }
+            }
+            // how to detect if both seq1 and seq2 match, but the rest doesn't
+            // match if seq1 is taken?
+            // **guess it doesn't matter here, since both sequences are guaranteed
+            // to consume the same number of characters since all choices are
+            // balanced.
+
+            if match_seq1 {
+                return Some(next_pos);
+            }
+
+            let mut next_pos = pos;
+            for rule in rule_seq2 {
+                match validate_rule(message, rules, rule, next_pos) {
+                    Some(p) => next_pos = p,
+                    None => return None,
+                }
+            }
+            Some(next_pos)
+        }
+    }
+}
+
+/// validate using recursive descent?
+pub fn is_valid(message: &str, rules: &Rules) -> bool {
+    if let Some(pos) = validate_rule(message, rules, &0, 0) {
+        pos == message.len()
+    } else {
+        false
+    }
+}
+
+/// Every way of joining one string from each set, in order.
+fn combine_seq(seq: &[HashSet<String>]) -> HashSet<String> {
+    let mut result: HashSet<String> = [String::new()].iter().cloned().collect();
+
+    for strings in seq {
+        let mut next = HashSet::new();
+        for prefix in &result {
+            for string in strings {
+                next.insert(format!("{}{}", prefix, string));
+            }
+        }
+        result = next;
+    }
+
+    result
+}
+
+/// All the strings a rule matches. Rules 8 and 11 loop in part two, so they
+/// match infinitely many.
+pub fn get_possible_strings(rules: &Rules, rule_id: &u32) -> Result<HashSet<String>, Error> {
+    if *rule_id == 8 || *rule_id == 11 {
+        return Err(Error::NoSolution(format!(
+            "rule {} loops, so the rules it's part of match infinitely many strings",
+            rule_id
+        )));
+    }
+
+    let result = match &rules[rule_id] {
+        Rule::Char(c) => {
+            let s = c.to_string();
+            let mut result = HashSet::new();
+            result.insert(s);
+            result
+        }
+        Rule::Seq(rule_seq) => {
+            let x = rule_seq
+                .iter()
+                .map(|id| get_possible_strings(rules, id))
+                .collect::<Result<Vec<_>, Error>>()?;
+            combine_seq(&x)
+        }
+        Rule::Choice(rule_seq1, rule_seq2) => {
+            let x1 = rule_seq1
+                .iter()
+                .map(|id| get_possible_strings(rules, id))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let x2 = rule_seq2
+                .iter()
+                .map(|id| get_possible_strings(rules, id))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let seq1 = combine_seq(&x1);
+            let seq2 = combine_seq(&x2);
+
+            let mut result = HashSet::new();
+            for s in seq1.union(&seq2) {
+                result.insert(s.clone());
+            }
+            result
+        }
+    };
+
+    Ok(result)
+}
+
+pub fn is_valid_partb(message: &str, rules: &Rules) -> bool {
+    // 0 -> 8 11
+    // 8 -> 42 | 42 8
+    // 11 -> 42 31 | 42 11 31
+    // so we keep matching 42s, then match 31s
+    // these are the only loops - no other rule goes to 8 or 11
+    // also needs to have at least 2 42 matches, and 31 must be less than 42.
+
+    let mut pos = 0;
+    let mut num_match_42 = 0;
+    while pos < message.len() {
+        match validate_rule(message, rules, &42, pos) {
+            Some(next_pos) => {
+                num_match_42 += 1;
+                trace!("match", rule = 42, from = pos, to = next_pos);
+                pos = next_pos;
+            }
+            None => break,
+        }
+    }
+
+    if num_match_42 < 2 || pos >= message.len() {
+        return false;
+    }
+
+    let mut num_match_31 = 0;
+    while pos < message.len() {
+        match validate_rule(message, rules, &31, pos) {
+            Some(next_pos) => {
+                num_match_31 += 1;
+                trace!("match", rule = 31, from = pos, to = next_pos);
+                pos = next_pos;
+            }
+            None => return false,
+        }
+    }
+
+    num_match_31 < num_match_42 && pos == message.len()
+}
+
+fn count_valid(puzzle: &Puzzle, is_valid_fn: fn(&str, &Rules) -> bool) -> usize {
+    let mut n_valid = 0;
+    for message in &puzzle.messages {
+        let valid = is_valid_fn(message, &puzzle.rules);
+        trace!("message", message = message.as_str(), valid = valid);
+        if valid {
+            log!("valid:   {}", message);
+            n_valid += 1;
+        } else {
+            log!("invalid: {}", message);
+        }
+    }
+
+    n_valid
+}
+
+/// Rules being made up, where every rule matches strings of just one length,
+/// and each branch of a choice is at most two rules long.
+struct Grammar {
+    rules: Rules,
+    by_length: HashMap<usize, Vec<u32>>,
+    free_ids: Vec<u32>,
+}
+
+impl Grammar {
+    /// Adds a rule matching strings of the given length, or reuses one. Single
+    /// letters always reuse the rules for `a` and `b`.
+    fn build(&mut self, rng: &mut Rng, length: usize) -> u32 {
+        if let Some(ids) = self.by_length.get(&length) {
+            if length == 1 || rng.chance(0.5) || self.free_ids.len() < 50 {
+                return *rng.choose(ids);
+            }
+        }
+
+        let mut branch = |rng: &mut Rng| {
+            let first = rng.below(length - 1) + 1;
+            vec![self.build(rng, first), self.build(rng, length - first)]
+        };
+        let first = branch(rng);
+        let rule = match rng.chance(0.5) {
+            true => match branch(rng) {
+                second if second != first => Rule::Choice(first, second),
+                _ => Rule::Seq(first),
+            },
+            false => Rule::Seq(first),
+        };
+        self.add(length, rule)
+    }
+
+    fn add(&mut self, length: usize, rule: Rule) -> u32 {
+        let id = self.free_ids.pop().expect("ran out of rule ids");
+        self.rules.insert(id, rule);
+        self.by_length.entry(length).or_default().push(id);
+        id
+    }
+
+    /// A random string matching the rule.
+    fn sample(&self, rng: &mut Rng, id: u32) -> String {
+        let ids = match &self.rules[&id] {
+            Rule::Char(c) => return c.to_string(),
+            Rule::Seq(ids) => ids,
+            Rule::Choice(ids1, ids2) => match rng.chance(0.5) {
+                true => ids1,
+                false => ids2,
+            },
+        };
+        ids.iter().map(|id| self.sample(rng, *id)).collect()
+    }
+}
+
+/// Makes up rules shaped like the real ones, where rule 0 is `8 11`, and
+/// rules 42 and 31 match strings of the given length (at least 2) that start
+/// with different letters, so they never match the same string.
+fn generate_grammar(rng: &mut Rng, length: usize) -> Grammar {
+    let mut free_ids: Vec<u32> = (1..1000)
+        .filter(|id| ![8, 11, 31, 42].contains(id))
+        .collect();
+    rng.shuffle(&mut free_ids);
+    let mut grammar = Grammar {
+        rules: HashMap::new(),
+        by_length: HashMap::new(),
+        free_ids,
+    };
+
+    let a = grammar.add(1, Rule::Char('a'));
+    let b = grammar.add(1, Rule::Char('b'));
+    let rest_42 = grammar.build(rng, length - 1);
+    let rest_31 = grammar.build(rng, length - 1);
+    grammar.rules.insert(42, Rule::Seq(vec![a, rest_42]));
+    grammar.rules.insert(31, Rule::Seq(vec![b, rest_31]));
+    grammar.rules.insert(0, Rule::Seq(vec![8, 11]));
+    grammar.rules.insert(8, Rule::Seq(vec![42]));
+    grammar.rules.insert(11, Rule::Seq(vec![42, 31]));
+
+    grammar
+}
+
+/// Makes up rules like the real ones, with 42 and 31 matching strings of 8
+/// letters, and messages to check against them.
+fn generate_puzzle(rng: &mut Rng, size: usize) -> String {
+    let grammar = generate_grammar(rng, 8);
+
+    let ids_text = |ids: &[u32]| {
+        let ids: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+        ids.join(" ")
+    };
+    let mut lines: Vec<_> = grammar
+        .rules
+        .iter()
+        .map(|(id, rule)| match rule {
+            Rule::Char(c) => format!("{}: \"{}\"", id, c),
+            Rule::Seq(ids) => format!("{}: {}", id, ids_text(ids)),
+            Rule::Choice(ids1, ids2) => {
+                format!("{}: {} | {}", id, ids_text(ids1), ids_text(ids2))
+            }
+        })
+        .collect();
+    // the rules are in a hash map, so sort them before shuffling to keep the
+    // same order for the same seed
+    lines.sort();
+    rng.shuffle(&mut lines);
+
+    let mut input = lines.join("\n") + "\n\n";
+    for _ in 0..size {
+        // some repeats of 42 then 31, which may or may not be valid
+        let n_42 = rng.range(1..=5);
+        let n_31 = rng.range(0..=4);
+        let mut message = Vec::new();
+        for idx in 0..n_42 + n_31 {
+            let id = if idx < n_42 { 42 } else { 31 };
+            message.extend(grammar.sample(rng, id).into_bytes());
+        }
+        if rng.chance(0.2) {
+            let idx = rng.below(message.len());
+            message[idx] = if message[idx] == b'a' { b'b' } else { b'a' };
+        }
+        input.push_str(std::str::from_utf8(&message).unwrap());
+        input.push('\n');
+    }
+
+    input
+}
+
+const EXAMPLE: &str = "\
+0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb
+";
+
+const LOOPING_EXAMPLE: &str = "\
+42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::check_agreement;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day19>(Part::BOTH);
+    }
+
+    #[test]
+    fn long_sequences() {
+        let input = "\
+0: 8 11
+8: 42
+11: 42 31
+42: 1 2 1
+31: 2 2 2
+1: \"a\"
+2: \"b\"
+
+abaababbb
+ababbb
+abaabaababbbbbb
+bbbaba
+";
+        assert_eq!(Day19::solve(input, Part::One).unwrap(), Answer::from(1));
+        assert_eq!(Day19::solve(input, Part::Two).unwrap(), Answer::from(2));
+
+        // rule 42 can't be expanded if it goes through rule 8, which loops
+        // in part two
+        let through_8 = input
+            .replace("42: 1 2 1", "42: 1 2 8")
+            .replace("8: 42", "8: 1");
+        assert!(Day19::check(&through_8).is_empty());
+        assert!(matches!(
+            Day19::solve(&through_8, Part::Two),
+            Err(Error::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn loops() {
+        let error = |input: &str| match Day19::parse(input) {
+            Err(Error::Parse(error)) => (error.line, error.column, error.message),
+            _ => panic!("expected a parse error"),
+        };
+
+        let self_loop = LOOPING_EXAMPLE.replace("42: 9 14 | 10 1", "42: 1 42");
+        assert_eq!(
+            error(&self_loop),
+            (1, 7, "rule 42 refers back to itself (42 -> 42)".to_string())
+        );
+        assert!(matches!(
+            Day19::solve(&self_loop, Part::Two),
+            Err(Error::Parse(_))
+        ));
+
+        let left_recursive = "0: 0 1\n1: \"a\"\n\naaa\n";
+        assert_eq!(
+            error(left_recursive),
+            (1, 4, "rule 0 refers back to itself (0 -> 0)".to_string())
+        );
+        assert!(matches!(
+            Day19::solve(left_recursive, Part::One),
+            Err(Error::Parse(_))
+        ));
+        assert!(matches!(
+            Day19::check(left_recursive).as_slice(),
+            [Error::Parse(error)] if error.line == 1
+        ));
+
+        // going round through other rules
+        let through_others = "0: 1 2\n1: \"a\"\n2: 1 3\n3: 2\n\naaa\n";
+        assert_eq!(
+            error(through_others),
+            (
+                4,
+                4,
+                "rule 2 refers back to itself (2 -> 3 -> 2)".to_string()
+            )
+        );
+
+        // rules 8 and 11 can loop the way part two has them
+        let part_two_rules = LOOPING_EXAMPLE
+            .replace("8: 42\n", "8: 42 | 42 8\n")
+            .replace("11: 42 31\n", "11: 42 31 | 42 11 31\n");
+        assert_eq!(
+            Day19::solve(&part_two_rules, Part::Two),
+            Ok(Answer::from(12))
+        );
+    }
+
+    #[test]
+    fn matching_agrees_with_expansion() {
+        // a rule without loops, and a message that may or may not match it
+        let generate = |rng: &mut Rng, size| {
+            let grammar = generate_grammar(rng, 1 + size);
+            let mut ids: Vec<u32> = grammar.rules.keys().copied().collect();
+            ids.retain(|id| ![0, 8, 11].contains(id));
+            ids.sort_unstable();
+
+            let id = *rng.choose(&ids);
+            let sampled_id = if rng.chance(0.5) {
+                id
+            } else {
+                *rng.choose(&ids)
+            };
+            let mut message = grammar.sample(rng, sampled_id);
+            if rng.chance(0.3) {
+                let idx = rng.below(message.len());
+                let c = if &message[idx..=idx] == "a" { "b" } else { "a" };
+                message.replace_range(idx..=idx, c);
+            }
+            (grammar.rules, id, message)
+        };
+        check_agreement(
+            300,
+            7,
+            generate,
+            |(rules, id, message)| get_possible_strings(rules, id).unwrap().contains(message),
+            |(rules, id, message)| validate_rule(message, rules, id, 0) == Some(message.len()),
+        );
+    }
+}
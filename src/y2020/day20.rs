@@ -0,0 +1,1190 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::Error;
+use crate::grid::{Grid, Pos};
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::render::Frame;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+use crate::trace;
+
+pub struct Day20;
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum Side {
+    Top,
+    Bottom,
+    Right,
+    Left,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tile {
+    pub sides: HashMap<Side, Vec<bool>>,
+    pub tile: Grid<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchDir {
+    Forward,
+    Backward,
+}
+
+pub type TileMap = HashMap<u32, Tile>;
+/// Which tile goes where, by (row, column).
+pub type Board = HashMap<(u32, u32), u32>;
+
+impl Solver for Day20 {
+    const DAY: u32 = 20;
+    const TITLE: &'static str = "Jurassic Jigsaw";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("20899048083289"),
+        part_two: Some("273"),
+        params: &[],
+    }];
+
+    /// Tiles along each side of the image, from 3 (so that a sea monster fits)
+    /// up to 12.
+    const INPUT_SIZE: usize = 12;
+
+    type Input = TileMap;
+
+    fn parse(input: &str) -> Result<TileMap, Error> {
+        read_input(input)
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        check_tiles(input)
+    }
+
+    fn warnings(input: &str) -> Vec<String> {
+        tile_warnings(input)
+    }
+
+    fn part_one(tiles: &TileMap, _params: &Params) -> Result<Answer, Error> {
+        find_corners(tiles).map(Answer::from)
+    }
+
+    fn part_two(tiles: &TileMap, _params: &Params) -> Result<Answer, Error> {
+        calc_part_b(tiles, &prepare_pattern()).map(Answer::from)
+    }
+
+    fn frames(tiles: &TileMap, part: Part, _params: &Params) -> Result<Vec<Frame>, Error> {
+        match part {
+            Part::One => Ok(Vec::new()),
+            Part::Two => Ok(vec![monster_frame(&assemble(tiles)?, &prepare_pattern())]),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_tiles(rng, size.clamp(3, 12)))
+    }
+}
+
+fn opposite_side(side: &Side) -> Side {
+    match side {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+        Side::Top => Side::Bottom,
+        Side::Bottom => Side::Top,
+    }
+}
+
+/// Splits a 10x10 tile into its sides and its inner 8x8 image.
+fn parse_tile(tile: Grid<bool>) -> Tile {
+    let mut sides = HashMap::new();
+    sides.insert(Side::Top, tile.row(0).to_vec());
+    sides.insert(Side::Bottom, tile.row(9).to_vec());
+    sides.insert(Side::Left, tile.column(0).copied().collect());
+    sides.insert(Side::Right, tile.column(9).copied().collect());
+
+    let inner = tile.subgrid((1, 1), 8, 8);
+
+    Tile { sides, tile: inner }
+}
+
+fn match_direction(a: &[bool], b: &[bool]) -> Option<MatchDir> {
+    let fwd_match = a.iter().zip(b.iter()).all(|(x, y)| x == y);
+    let bkd_match = a.iter().rev().zip(b.iter()).all(|(x, y)| x == y);
+
+    if fwd_match {
+        Some(MatchDir::Forward)
+    } else if bkd_match {
+        Some(MatchDir::Backward)
+    } else {
+        None
+    }
+}
+
+/// count the number of sides with matches for the given tile
+fn count_matches(tile_id: &u32, tile: &Tile, tiles: &TileMap) -> i32 {
+    let mut n_match = 0;
+    for row in tile.sides.values() {
+        let mut has_match = false;
+        for (tile_id2, tile2) in tiles {
+            if tile_id2 == tile_id {
+                continue;
+            }
+
+            for row2 in tile2.sides.values() {
+                if match_direction(row, row2).is_some() {
+                    has_match = true;
+                    break;
+                }
+            }
+            if has_match {
+                break;
+            }
+        }
+
+        if has_match {
+            n_match += 1;
+        }
+    }
+
+    n_match
+}
+
+/// find all matches in the tilemap, ignoring tiles with the same id.
+/// a side that matches more than one other tile can't be fitted.
+fn get_all_matches(
+    tile_id: &u32,
+    tile: &Tile,
+    tiles: &TileMap,
+) -> Result<HashMap<Side, (u32, Side, MatchDir)>, Error> {
+    let mut all_matches: HashMap<Side, (u32, Side, MatchDir)> = HashMap::new();
+    for (side, row) in &tile.sides {
+        let mut matches = Vec::new();
+        for (tile_id2, tile2) in tiles {
+            if tile_id2 == tile_id {
+                continue;
+            }
+
+            for (side2, row2) in &tile2.sides {
+                if let Some(dir) = match_direction(row, row2) {
+                    matches.push((*tile_id2, side2.clone(), dir));
+                    break;
+                }
+            }
+        }
+
+        if matches.len() > 1 {
+            return Err(Error::NoSolution(format!(
+                "the {} side of tile {} matches more than one other tile",
+                format!("{:?}", side).to_lowercase(),
+                tile_id
+            )));
+        }
+
+        if matches.len() == 1 {
+            all_matches.insert(side.clone(), matches[0].clone());
+        }
+    }
+
+    Ok(all_matches)
+}
+
+/// The number of tiles along each side of the (square) image.
+fn grid_side(tiles: &TileMap) -> usize {
+    (tiles.len() as f64).sqrt().round() as usize
+}
+
+/// Get part A answer.
+pub fn find_corners(tiles: &TileMap) -> Result<u64, Error> {
+    // count number of sides with matches for each tile.
+    // if there are tiles that have only 2 sides with matches,
+    // then those must be the corners.
+    let mut n_corners = 0;
+    let mut n_sides = 0;
+
+    let mut prod: u64 = 1;
+    for (tile_id, tile) in tiles {
+        let n_matches = count_matches(tile_id, tile, tiles);
+        trace!("tile", tile = *tile_id, matches = n_matches);
+
+        if n_matches == 2 {
+            n_corners += 1;
+            prod = prod.checked_mul(*tile_id as u64).ok_or_else(|| {
+                Error::NoSolution("the product of the corner ids is too big".to_string())
+            })?;
+            log!("CORNER! tile {}: {} matches", tile_id, n_matches);
+        } else if n_matches == 3 {
+            n_sides += 1;
+        }
+    }
+    log!("# corners: {}, # sides: {}", n_corners, n_sides);
+    if n_corners != 4 {
+        return Err(Error::NoSolution(format!(
+            "there should be 4 corner tiles, but {} tiles match only two others",
+            n_corners
+        )));
+    }
+    Ok(prod)
+}
+
+/// Flips the tile on the horizontal axis.
+fn flip_horizontal(tile: &Tile) -> Tile {
+    let mut new_sides = HashMap::new();
+    new_sides.insert(Side::Bottom, tile.sides.get(&Side::Top).unwrap().clone());
+    new_sides.insert(Side::Top, tile.sides.get(&Side::Bottom).unwrap().clone());
+    new_sides.insert(
+        Side::Left,
+        tile.sides
+            .get(&Side::Left)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+    new_sides.insert(
+        Side::Right,
+        tile.sides
+            .get(&Side::Right)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+
+    let new_tile = tile.tile.flip_horizontal();
+
+    Tile {
+        sides: new_sides,
+        tile: new_tile,
+    }
+}
+
+/// Flips the tile on the vertical axis.
+fn flip_vertical(tile: &Tile) -> Tile {
+    let mut new_sides = HashMap::new();
+    new_sides.insert(Side::Left, tile.sides.get(&Side::Right).unwrap().clone());
+    new_sides.insert(Side::Right, tile.sides.get(&Side::Left).unwrap().clone());
+    new_sides.insert(
+        Side::Bottom,
+        tile.sides
+            .get(&Side::Bottom)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+    new_sides.insert(
+        Side::Top,
+        tile.sides
+            .get(&Side::Top)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+
+    let new_tile = tile.tile.flip_vertical();
+
+    Tile {
+        sides: new_sides,
+        tile: new_tile,
+    }
+}
+
+/// Rotates tile 90 degrees clockwise.
+fn rotate_tile_clockwise(tile: &Tile) -> Tile {
+    let mut new_sides = HashMap::new();
+
+    new_sides.insert(
+        Side::Top,
+        tile.sides
+            .get(&Side::Left)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+    new_sides.insert(Side::Right, tile.sides.get(&Side::Top).unwrap().clone());
+    new_sides.insert(
+        Side::Bottom,
+        tile.sides
+            .get(&Side::Right)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+    new_sides.insert(Side::Left, tile.sides.get(&Side::Bottom).unwrap().clone());
+
+    let new_tile = tile.tile.rotate_clockwise();
+
+    Tile {
+        sides: new_sides,
+        tile: new_tile,
+    }
+}
+
+/// Rotates tile 90 degrees anti-clockwise.
+fn rotate_anticlockwise(tile: &Tile) -> Tile {
+    let mut new_sides = HashMap::new();
+
+    new_sides.insert(Side::Top, tile.sides.get(&Side::Right).unwrap().clone());
+    new_sides.insert(
+        Side::Right,
+        tile.sides
+            .get(&Side::Bottom)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+    new_sides.insert(Side::Bottom, tile.sides.get(&Side::Left).unwrap().clone());
+    new_sides.insert(
+        Side::Left,
+        tile.sides
+            .get(&Side::Top)
+            .unwrap()
+            .clone()
+            .into_iter()
+            .rev()
+            .collect(),
+    );
+
+    let new_tile = tile.tile.rotate_anticlockwise();
+
+    Tile {
+        sides: new_sides,
+        tile: new_tile,
+    }
+}
+
+fn get_start_id(tiles: &TileMap) -> Option<u32> {
+    let mut start_id = None;
+    for (tile_id, tile) in tiles {
+        let n_matches = count_matches(tile_id, tile, tiles);
+        trace!("tile", tile = *tile_id, matches = n_matches);
+        if n_matches == 2 {
+            start_id = Some(*tile_id);
+        }
+    }
+
+    start_id
+}
+
+fn add_match(
+    tiles: &TileMap,
+    new_tiles: &mut TileMap,
+    current_id: &u32,
+    match_side: &Side,
+) -> Result<u32, Error> {
+    let current_tile = new_tiles.get(current_id).unwrap().clone();
+
+    for (side, (other_id, other_side, match_dir)) in
+        get_all_matches(current_id, &current_tile, tiles)?
+    {
+        if side != *match_side {
+            continue;
+        }
+
+        // flip/rotate the new tile as appropriate
+        let next_tile = tiles.get(&other_id).unwrap();
+
+        let fixed_tile = match (&side, &other_side, &match_dir) {
+            (Side::Right, Side::Left, MatchDir::Forward)
+            | (Side::Bottom, Side::Top, MatchDir::Forward) => next_tile.clone(),
+
+            (Side::Right, Side::Left, MatchDir::Backward)
+            | (Side::Bottom, Side::Bottom, MatchDir::Forward) => flip_horizontal(next_tile),
+
+            (Side::Right, Side::Top, MatchDir::Backward)
+            | (Side::Bottom, Side::Right, MatchDir::Forward) => rotate_anticlockwise(next_tile),
+
+            (Side::Right, Side::Bottom, MatchDir::Backward)
+            | (Side::Bottom, Side::Right, MatchDir::Backward) => {
+                flip_horizontal(&rotate_tile_clockwise(next_tile))
+            }
+
+            (Side::Right, Side::Right, MatchDir::Forward)
+            | (Side::Bottom, Side::Top, MatchDir::Backward) => flip_vertical(next_tile),
+
+            (Side::Right, Side::Right, MatchDir::Backward)
+            | (Side::Bottom, Side::Bottom, MatchDir::Backward) => {
+                rotate_tile_clockwise(&rotate_tile_clockwise(next_tile))
+            }
+
+            (Side::Right, Side::Bottom, MatchDir::Forward)
+            | (Side::Bottom, Side::Left, MatchDir::Backward) => rotate_tile_clockwise(next_tile),
+
+            (Side::Right, Side::Top, MatchDir::Forward)
+            | (Side::Bottom, Side::Left, MatchDir::Forward) => {
+                flip_vertical(&rotate_tile_clockwise(next_tile))
+            }
+
+            // tiles are only ever added to the right or the bottom
+            (Side::Top, _, _) | (Side::Left, _, _) => {
+                return Err(Error::NoSolution(format!(
+                    "can't add a tile to the {} of tile {}",
+                    format!("{:?}", side).to_lowercase(),
+                    current_id
+                )))
+            }
+        };
+
+        let fits = match_direction(
+            current_tile.sides.get(match_side).unwrap(),
+            fixed_tile.sides.get(&opposite_side(match_side)).unwrap(),
+        ) == Some(MatchDir::Forward);
+        if !fits || new_tiles.contains_key(&other_id) {
+            return Err(Error::NoSolution(format!(
+                "tile {} doesn't fit next to tile {}",
+                other_id, current_id
+            )));
+        }
+        new_tiles.insert(other_id, fixed_tile);
+
+        return Ok(other_id);
+    }
+
+    Err(Error::NoSolution(format!(
+        "nothing matches the {} side of tile {}",
+        format!("{:?}", match_side).to_lowercase(),
+        current_id
+    )))
+}
+
+/// compute offsets for the pattern for easier matching later,
+/// as positions relative to its top left corner.
+pub fn prepare_pattern() -> Vec<Pos> {
+    let pattern = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+    log!("{}", pattern);
+    let lines: Vec<_> = pattern.split('\n').collect();
+
+    log!("# rows: {}", lines.len());
+    for line in &lines {
+        log!("# cols: {}", line.len());
+    }
+
+    let mut offsets = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            if c == '#' {
+                offsets.push((i, j));
+            }
+        }
+    }
+
+    log!("{:?}", offsets);
+    offsets
+}
+
+pub fn build_image(tiles: &TileMap, tile_positions: &Board, side: usize) -> Grid<bool> {
+    let mut image = Grid::new(side * 8, side * 8, false);
+
+    for row in 0..(side as u32) {
+        for col in 0..(side as u32) {
+            let tile_id = tile_positions.get(&(row, col)).unwrap();
+            let tile = tiles.get(tile_id).unwrap();
+
+            for ((r, c), pixel) in tile.tile.iter() {
+                image[(row as usize * 8 + r, col as usize * 8 + c)] = *pixel;
+            }
+        }
+    }
+
+    image
+}
+
+/// fits tiles together, rotating/flipping them as necessary.
+/// returns a mapping of coordinates to tile_ids and the rotated tiles.
+pub fn fit_tiles(tiles: &TileMap) -> Result<(Board, TileMap), Error> {
+    let side = grid_side(tiles) as u32;
+
+    // first, find a corner, then flip/rotate so that its sides
+    // with matches are at the right and bottom.
+
+    let mut rotated_tiles: TileMap = HashMap::new();
+
+    let start_id = get_start_id(tiles).ok_or_else(|| {
+        Error::NoSolution("there's no corner tile, with only two sides that match".to_string())
+    })?;
+
+    let start_tile = tiles.get(&start_id).unwrap();
+    let start_matches = get_all_matches(&start_id, start_tile, tiles)?;
+
+    let fixed_start_tile = match (
+        start_matches.contains_key(&Side::Left),
+        start_matches.contains_key(&Side::Right),
+        start_matches.contains_key(&Side::Top),
+        start_matches.contains_key(&Side::Bottom),
+    ) {
+        (false, true, false, true) => start_tile.clone(),
+        (true, false, false, true) => flip_vertical(start_tile),
+        (false, true, true, false) => flip_horizontal(start_tile),
+        (true, false, true, false) => flip_horizontal(&flip_vertical(start_tile)),
+        _ => {
+            return Err(Error::NoSolution(format!(
+                "the sides of corner tile {} that match aren't next to each other",
+                start_id
+            )))
+        }
+    };
+
+    rotated_tiles.insert(start_id, fixed_start_tile);
+    let mut board = HashMap::new();
+
+    board.insert((0, 0), start_id);
+    trace!("place", tile = start_id, row = 0, col = 0);
+
+    // keep going right until we reach a corner, then go to the next row
+    // repeat until done.
+
+    let mut row_start_id = start_id;
+    for row in 0..side {
+        let mut curr_id = row_start_id;
+        for col in 1..side {
+            let next_id = add_match(tiles, &mut rotated_tiles, &curr_id, &Side::Right)?;
+
+            board.insert((row, col), next_id);
+            trace!("place", tile = next_id, row = row, col = col);
+            curr_id = next_id;
+        }
+
+        if row < side - 1 {
+            let next_id = add_match(tiles, &mut rotated_tiles, &row_start_id, &Side::Bottom)?;
+
+            row_start_id = next_id;
+            board.insert((row + 1, 0), next_id);
+            trace!("place", tile = next_id, row = row + 1, col = 0);
+        }
+    }
+
+    Ok((board, rotated_tiles))
+}
+
+/// The top left corners of the sea monsters in the image.
+fn find_monsters(image: &Grid<bool>, offsets: &[Pos]) -> Vec<Pos> {
+    image
+        .positions()
+        .filter(|(row, col)| {
+            offsets
+                .iter()
+                .all(|(r, c)| image.get((row + r, col + c)) == Some(&true))
+        })
+        .collect()
+}
+
+/// Counts the sea monsters in the image, returning the number of monsters
+/// and how many cells are not part of a monster.
+pub fn num_monsters(image: &Grid<bool>, offsets: &[Pos]) -> (i32, i32) {
+    let monsters = find_monsters(image, offsets);
+    for (row, col) in &monsters {
+        log!("found match at ({}, {})", row, col);
+    }
+    let n_match = monsters.len() as i32;
+
+    let total_occupied = image.count(|cell| *cell) as i32;
+
+    if n_match > 0 {
+        log!("# matches: {}", n_match);
+    }
+
+    (n_match, total_occupied - n_match * (offsets.len() as i32))
+}
+
+pub fn calc_part_b(tiles: &TileMap, offsets: &[Pos]) -> Result<i32, Error> {
+    // calculate number of matching sides.
+    // seems each side only matches at most one other side.
+    let mut n_sides = HashMap::new();
+
+    for (tile_id, tile) in tiles {
+        let n_matches = count_matches(tile_id, tile, tiles);
+        trace!("tile", tile = *tile_id, matches = n_matches);
+        get_all_matches(tile_id, tile, tiles)?;
+        n_sides.insert(*tile_id, n_matches);
+    }
+
+    for (tile_id, _) in n_sides.iter().filter(|(_, n_matches)| **n_matches == 2) {
+        log!("{}", tile_id);
+        let matches = get_all_matches(tile_id, tiles.get(tile_id).unwrap(), tiles)?;
+
+        log!("matches:");
+        for (side, (other_id, other_side, match_dir)) in &matches {
+            log!(
+                "{:?}: [{} {:?} {:?}]",
+                side,
+                other_id,
+                other_side,
+                match_dir
+            );
+        }
+        log!();
+    }
+
+    // construct the final image, then rotate and flip
+    // until we see monsters.
+
+    let image = assemble(tiles)?;
+
+    for rot_image in image.orientations() {
+        let (n_match, roughness) = num_monsters(&rot_image, offsets);
+        if n_match > 0 {
+            return Ok(roughness);
+        }
+    }
+
+    Err(Error::NoSolution(
+        "no sea monsters found in any orientation".to_string(),
+    ))
+}
+
+/// Fits the tiles together into the final image, with the borders removed.
+fn assemble(tiles: &TileMap) -> Result<Grid<bool>, Error> {
+    // pick one corner to put at (0, 0),
+    // then slowly fill in the sides.
+    // then fill in the middle
+
+    let (positions, new_tiles) = fit_tiles(tiles)?;
+    Ok(build_image(&new_tiles, &positions, grid_side(tiles)))
+}
+
+/// Draws the image the way round that the sea monsters are, with the monsters
+/// as `O`s like in the puzzle. If there aren't any, it's drawn as it is.
+fn monster_frame(image: &Grid<bool>, offsets: &[Pos]) -> Frame {
+    let orientations = image.orientations();
+    let (image, monsters) = orientations
+        .iter()
+        .map(|image| (image, find_monsters(image, offsets)))
+        .find(|(_, monsters)| !monsters.is_empty())
+        .unwrap_or((image, Vec::new()));
+
+    let mut frame = image.map(|pixel| if *pixel { '#' } else { '.' });
+    for (row, col) in monsters {
+        for (r, c) in offsets {
+            frame[(row + r, col + c)] = 'O';
+        }
+    }
+    frame
+}
+
+fn read_input(input: &str) -> Result<TileMap, Error> {
+    let mut tiles = HashMap::new();
+
+    let mut lines = parse::lines(input).filter(|line| !line.text.trim().is_empty());
+
+    let mut n_tiles = 0;
+    while let Some(title) = lines.next() {
+        let (title_id, id_text) = parse_title(&title)?;
+
+        let rows: Vec<_> = lines.by_ref().take(10).collect();
+        for line in &rows {
+            let text = line.text.trim();
+            if text.chars().count() != 10 {
+                return Err(line.error(text, "tiles are 10 pixels wide"));
+            }
+        }
+        let tile = parse_rows(rows)?;
+        if tile.height() != 10 {
+            let message = format!("tile {} should have 10 rows", title_id);
+            return Err(parse::end_of_input(input, message));
+        }
+
+        if tiles.insert(title_id, parse_tile(tile)).is_some() {
+            return Err(title.error(id_text, format!("tile {} appears twice", title_id)));
+        }
+        n_tiles += 1;
+    }
+
+    let side = grid_side(&tiles);
+    if side * side != n_tiles {
+        let message = format!("the tiles should form a square, but there are {}", n_tiles);
+        return Err(parse::end_of_input(input, message));
+    }
+    // anything smaller has no middle tiles, and no room for a sea monster
+    if side < 3 {
+        let message = format!(
+            "the tiles should be at least 3 by 3, not {} by {}",
+            side, side
+        );
+        return Err(parse::end_of_input(input, message));
+    }
+
+    log!("# tiles: {}", n_tiles);
+    Ok(tiles)
+}
+
+/// Reads the id from a `Tile <id>:` line, along with the id's text.
+fn parse_title<'a>(title: &Line<'a>) -> Result<(u32, &'a str), Error> {
+    let id_text = match title
+        .text
+        .trim()
+        .strip_prefix("Tile ")
+        .and_then(|rest| rest.strip_suffix(':'))
+    {
+        Some(id_text) => id_text,
+        None => return Err(title.error(title.text, "expected `Tile <id>:`")),
+    };
+    Ok((title.parse::<u32>(id_text)?, id_text))
+}
+
+fn parse_rows(rows: Vec<Line>) -> Result<Grid<bool>, Error> {
+    let pixel = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    Grid::parse_lines(rows.into_iter(), pixel, "expected `#` or `.`")
+}
+
+/// Every problem with the tiles, carrying on with the next tile after a bad
+/// one. As well as what `read_input` checks (including that there are at
+/// least 3 by 3 tiles), the sides have to fit together the way
+/// `find_corners` and `assemble` expect: no side matches more than one other
+/// tile, and exactly four tiles (the corners) have only two sides that
+/// match.
+fn check_tiles(input: &str) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let mut tiles = HashMap::new();
+    let mut titles = Vec::new();
+    let mut n_tiles = 0;
+
+    let is_title = |line: &Line| line.text.trim().starts_with("Tile ");
+    let mut lines = parse::lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .peekable();
+    while let Some(title) = lines.next() {
+        // a tile is everything up to the next title
+        let mut rows = Vec::new();
+        while let Some(row) = lines.next_if(|line| !is_title(line)) {
+            rows.push(row);
+        }
+        n_tiles += 1;
+        let n_errors = errors.len();
+
+        let (id, id_text) = match parse_title(&title) {
+            Ok(id) => id,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        if rows.len() != 10 {
+            let message = format!("tile {} has {} rows, not 10", id, rows.len());
+            errors.push(title.error(title.text.trim(), message));
+        }
+        for row in &rows {
+            let text = row.text.trim();
+            if text.chars().count() != 10 {
+                errors.push(row.error(text, "tiles are 10 pixels wide"));
+            } else if let Err(error) = parse_rows(vec![*row]) {
+                errors.push(error);
+            }
+        }
+        if let Some((_, first)) = titles.iter().find(|(other, _)| *other == id) {
+            let message = format!("tile {} is already on line {}", id, first);
+            errors.push(title.error(id_text, message));
+            continue;
+        }
+        titles.push((id, title.number));
+
+        if errors.len() == n_errors {
+            if let Ok(tile) = parse_rows(rows) {
+                tiles.insert(id, (title, parse_tile(tile)));
+            }
+        }
+    }
+
+    let side = (n_tiles as f64).sqrt().round() as usize;
+    if side * side != n_tiles {
+        let message = format!("the tiles should form a square, but there are {}", n_tiles);
+        errors.push(parse::end_of_input(input, message));
+    } else if side < 3 {
+        let message = format!(
+            "the tiles should be at least 3 by 3, not {} by {}",
+            side, side
+        );
+        errors.push(parse::end_of_input(input, message));
+    }
+    // whether the sides fit only means something once every tile is there
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    let mut n_corners = 0;
+    for (id, _) in &titles {
+        let (title, tile) = &tiles[id];
+        let mut n_matching = 0;
+        for side in &[Side::Top, Side::Right, Side::Bottom, Side::Left] {
+            let pixels = &tile.sides[side];
+            let matches: Vec<_> = titles
+                .iter()
+                .filter(|(other, _)| other != id)
+                .filter(|(other, _)| {
+                    let mut sides = tiles[other].1.sides.values();
+                    sides.any(|edge| match_direction(pixels, edge).is_some())
+                })
+                .map(|(other, _)| other.to_string())
+                .collect();
+            if matches.len() > 1 {
+                let message = format!(
+                    "the {} side of tile {} matches more than one other tile ({})",
+                    format!("{:?}", side).to_lowercase(),
+                    id,
+                    matches.join(", ")
+                );
+                errors.push(title.error(title.text.trim(), message));
+            }
+            if !matches.is_empty() {
+                n_matching += 1;
+            }
+        }
+        if n_matching == 2 {
+            n_corners += 1;
+        }
+    }
+    if n_corners != 4 {
+        let message = format!(
+            "there should be 4 corner tiles, with only two sides that match other tiles, but there are {}",
+            n_corners
+        );
+        errors.push(parse::end_of_input(input, message));
+    }
+
+    errors
+}
+
+/// What's different about the tiles from a real input's: those have 144
+/// tiles, in a 12 by 12 square, with four-digit ids.
+fn tile_warnings(input: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut n_tiles = 0;
+    for title in parse::lines(input).filter(|line| line.text.trim().starts_with("Tile ")) {
+        n_tiles += 1;
+        if let Ok((id, _)) = parse_title(&title) {
+            if !(1000..10000).contains(&id) {
+                warnings.push(format!(
+                    "tile {} on line {} doesn't have a four-digit id",
+                    id, title.number
+                ));
+            }
+        }
+    }
+    if n_tiles != 144 {
+        warnings.push(format!(
+            "there are {} tiles, rather than 144 in a 12 by 12 square",
+            n_tiles
+        ));
+    }
+
+    warnings
+}
+
+/// Makes up an image with sea monsters in it, and cuts it into `side` by
+/// `side` tiles, each with a random id and rotated or flipped at random.
+fn generate_tiles(rng: &mut Rng, side: usize) -> String {
+    let image = generate_image(rng, side * 8);
+
+    // neighbouring tiles share their borders: tile (row, col) is the 10x10
+    // part with its top left corner at (9 * row, 9 * col), and the 8x8 part
+    // inside the border is its part of the image
+    let width = 9 * side + 1;
+    let mut pixels = Grid::new(width, width, false);
+    for ((row, col), pixel) in image.iter() {
+        pixels[(row / 8 * 9 + 1 + row % 8, col / 8 * 9 + 1 + col % 8)] = *pixel;
+    }
+    generate_borders(rng, &mut pixels, side);
+
+    let mut ids: Vec<u32> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+    let mut tiles = Vec::new();
+    for (idx, id) in ids.iter().take(side * side).enumerate() {
+        let tile = pixels.subgrid((idx / side * 9, idx % side * 9), 10, 10);
+        let orientations = tile.orientations();
+        let tile = rng.choose(&orientations);
+        let pixels = tile.render(|pixel| if *pixel { '#' } else { '.' });
+        tiles.push(format!("Tile {}:\n{}", id, pixels));
+    }
+    rng.shuffle(&mut tiles);
+
+    tiles.join("\n")
+}
+
+/// A random image with some sea monsters that don't overlap, in some
+/// orientation.
+fn generate_image(rng: &mut Rng, width: usize) -> Grid<bool> {
+    let monster = prepare_pattern();
+    let rows = (0..width).map(|_| (0..width).map(|_| rng.chance(0.25)).collect());
+    let mut image = Grid::from_rows(rows.collect());
+
+    let mut placed: Vec<Pos> = Vec::new();
+    for _ in 0..(width * width / 300).max(1) {
+        for _ in 0..100 {
+            let (row, col) = (rng.below(width - 2), rng.below(width - 19));
+            let clear = placed
+                .iter()
+                .all(|(r, c)| r.abs_diff(row) >= 3 || c.abs_diff(col) >= 20);
+            if clear {
+                for (r, c) in &monster {
+                    image[(row + r, col + c)] = true;
+                }
+                placed.push((row, col));
+                break;
+            }
+        }
+    }
+
+    let orientations = image.orientations();
+    rng.choose(&orientations).clone()
+}
+
+/// Fills in the borders between the tiles so that no two borders match, even
+/// reversed, and none reads the same both ways, so there's only one way to
+/// fit the tiles together.
+fn generate_borders(rng: &mut Rng, pixels: &mut Grid<bool>, side: usize) {
+    let mut borders: Vec<Vec<Pos>> = Vec::new();
+    for line in 0..=side {
+        for tile in 0..side {
+            borders.push((0..10).map(|k| (9 * line, 9 * tile + k)).collect());
+            borders.push((0..10).map(|k| (9 * tile + k, 9 * line)).collect());
+        }
+    }
+    for pos in borders.iter().flatten() {
+        pixels[*pos] = rng.chance(0.5);
+    }
+
+    // redraw any border that's a palindrome or matches an earlier one, until
+    // none do. The corners are shared with other borders, so leave them.
+    let mut done = false;
+    while !done {
+        done = true;
+        let mut seen = HashSet::new();
+        for border in &borders {
+            let forward: Vec<_> = border.iter().map(|pos| pixels[*pos]).collect();
+            let backward: Vec<_> = forward.iter().rev().copied().collect();
+            if forward == backward || !seen.insert(forward.min(backward)) {
+                for pos in &border[1..9] {
+                    pixels[*pos] = rng.chance(0.5);
+                }
+                done = false;
+            }
+        }
+    }
+}
+
+const EXAMPLE: &str = "\
+Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day20>(Part::BOTH);
+    }
+
+    #[test]
+    fn monsters_are_highlighted() {
+        let tiles = Day20::parse(EXAMPLE).unwrap();
+        let frame = monster_frame(&assemble(&tiles).unwrap(), &prepare_pattern());
+        assert_eq!(frame.count(|c| *c == 'O'), 2 * 15);
+        assert_eq!(frame.count(|c| *c == '#'), 273);
+    }
+
+    #[test]
+    fn unsolvable() {
+        let tile = |id: usize| -> String {
+            let rows: Vec<_> = EXAMPLE.lines().skip(1).take(10).collect();
+            format!("Tile {}:\n{}\n\n", id, rows.join("\n"))
+        };
+
+        // too few tiles to have any in the middle
+        let input: String = (1..=4).map(tile).collect();
+        assert!(matches!(Day20::parse(&input), Err(Error::Parse(_))));
+
+        // every side of every tile matches every other tile
+        let input: String = (1..=9).map(tile).collect();
+        let tiles = Day20::parse(&input).unwrap();
+        for part in Part::BOTH {
+            let result = Day20::solve_parsed(&tiles, *part, &Params::new());
+            assert!(matches!(result, Err(Error::NoSolution(_))), "{:?}", result);
+        }
+    }
+
+    #[test]
+    fn checks() {
+        let lines = |input: &str| -> Vec<(usize, String)> {
+            Day20::check(input)
+                .into_iter()
+                .map(|error| match error {
+                    Error::Parse(error) => (error.line, error.message),
+                    error => panic!("unexpected error {:?}", error),
+                })
+                .collect()
+        };
+        assert_eq!(lines(EXAMPLE), []);
+
+        let broken = EXAMPLE
+            .replacen("####.#...#\n", "####.#...\n", 1)
+            .replacen("##.##.###.\n", "##.##.#x#.\n", 1)
+            .replacen("Tile 1951:", "Tile 2311:", 1);
+        assert_eq!(
+            lines(&broken),
+            [
+                (5, "tiles are 10 pixels wide".to_string()),
+                (6, "expected `#` or `.`".to_string()),
+                (13, "tile 2311 is already on line 1".to_string()),
+            ]
+        );
+
+        // the tiles are all fine, but there aren't enough to fit together
+        let one_tile: String = EXAMPLE
+            .lines()
+            .take(11)
+            .map(|l| l.to_string() + "\n")
+            .collect();
+        assert_eq!(
+            lines(&one_tile),
+            [(
+                12,
+                "the tiles should be at least 3 by 3, not 1 by 1".to_string()
+            )]
+        );
+
+        // nine tiles that all fit together every way
+        let tile = |id: usize| -> String { format!("Tile {}:\n{}\n\n", id, &one_tile[11..]) };
+        let same_tiles: String = (1001..=1009).map(tile).collect();
+        assert_eq!(
+            lines(&same_tiles).last().unwrap().1,
+            "there should be 4 corner tiles, with only two sides that match other tiles, \
+             but there are 0"
+        );
+    }
+
+    #[test]
+    fn warnings() {
+        assert_eq!(
+            Day20::warnings(EXAMPLE),
+            ["there are 9 tiles, rather than 144 in a 12 by 12 square"]
+        );
+
+        let short_id = EXAMPLE.replacen("Tile 1951:", "Tile 195:", 1);
+        assert_eq!(
+            Day20::warnings(&short_id)[0],
+            "tile 195 on line 13 doesn't have a four-digit id"
+        );
+    }
+}
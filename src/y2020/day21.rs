@@ -0,0 +1,258 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Line};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day21;
+
+pub type Food = (HashSet<String>, HashSet<String>);
+
+impl Solver for Day21 {
+    const DAY: u32 = 21;
+    const TITLE: &'static str = "Allergen Assessment";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("5"),
+        part_two: Some("mxmxvkd,sqjhc,fvjkl"),
+        params: &[],
+    }];
+
+    /// Foods on the list.
+    const INPUT_SIZE: usize = 40;
+
+    type Input = Vec<Food>;
+
+    fn parse(input: &str) -> Result<Vec<Food>, Error> {
+        parse::lines(input).map(parse_food).collect()
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, parse_food)
+    }
+
+    fn part_one(foods: &Vec<Food>, _params: &Params) -> Result<Answer, Error> {
+        let (all_ingredients, non_allergen) = find_non_allergens(foods);
+
+        // find ingredients that cannot contain any allergens
+        let mut non_allergen_ingredients = all_ingredients;
+        for ingredients in non_allergen.values() {
+            non_allergen_ingredients.retain(|i| ingredients.contains(i));
+        }
+
+        let mut answer = 0;
+        for (ingredients, _) in foods {
+            answer += ingredients.intersection(&non_allergen_ingredients).count();
+        }
+        Ok(answer.into())
+    }
+
+    fn part_two(foods: &Vec<Food>, _params: &Params) -> Result<Answer, Error> {
+        let assigned = assign_allergens(foods)?;
+
+        let dangerous: Vec<_> = assigned.values().map(|s| s.as_str()).collect();
+        Ok(dangerous.join(",").into())
+    }
+
+    fn details(foods: &Vec<Food>, part: Part, _params: &Params) -> Option<Json> {
+        if part != Part::Two {
+            return None;
+        }
+
+        let assigned = assign_allergens(foods).ok()?;
+        let allergens = assigned
+            .into_iter()
+            .map(|(allergen, ingredient)| (allergen, ingredient.into()));
+        Some(Json::object(vec![("allergens", Json::object(allergens))]))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_foods(rng, size))
+    }
+}
+
+/// Works out which ingredient contains each allergen, sorted by allergen.
+pub fn assign_allergens(foods: &[Food]) -> Result<BTreeMap<String, String>, Error> {
+    let (all_ingredients, non_allergen) = find_non_allergens(foods);
+
+    let mut possible: HashMap<&String, HashSet<&String>> = non_allergen
+        .iter()
+        .map(|(allergen, ingredients)| {
+            (allergen, all_ingredients.difference(ingredients).collect())
+        })
+        .collect();
+
+    // repeatedly fix an allergen that has only one possible ingredient,
+    // and remove that ingredient from the other allergens.
+    let mut assigned = BTreeMap::new();
+    while !possible.is_empty() {
+        let (allergen, ingredient) = match possible.iter().find(|(_, i)| i.len() == 1) {
+            Some((allergen, ingredients)) => (*allergen, *ingredients.iter().next().unwrap()),
+            None => {
+                return Err(Error::NoSolution(
+                    "could not narrow down the remaining allergens".to_string(),
+                ))
+            }
+        };
+
+        log!("{}: {}", allergen, ingredient);
+        possible.remove(allergen);
+        for ingredients in possible.values_mut() {
+            ingredients.remove(ingredient);
+        }
+        assigned.insert(allergen.clone(), ingredient.clone());
+    }
+
+    Ok(assigned)
+}
+
+fn parse_food(line: Line) -> Result<Food, Error> {
+    let text = line.text.trim();
+
+    let (ingredients, allergens) = match text.find('(') {
+        Some(idx) => {
+            let list = &text[idx..];
+            let allergens = match list
+                .strip_prefix("(contains ")
+                .and_then(|list| list.strip_suffix(')'))
+            {
+                Some(allergens) => allergens,
+                None => return Err(line.error(list, "expected `(contains <allergens>)`")),
+            };
+            (
+                &text[..idx],
+                allergens.split(',').map(|s| s.trim().to_string()).collect(),
+            )
+        }
+        None => (text, HashSet::new()),
+    };
+
+    let ingredients: HashSet<String> = ingredients
+        .split_whitespace()
+        .map(|s| s.to_string())
+        .collect();
+    if ingredients.is_empty() {
+        return Err(line.error(text, "expected a list of ingredients"));
+    }
+
+    Ok((ingredients, allergens))
+}
+
+/// Returns all ingredients, and for each allergen, the ingredients that
+/// cannot contain it.
+pub fn find_non_allergens(foods: &[Food]) -> (HashSet<String>, HashMap<String, HashSet<String>>) {
+    let mut all_ingredients = HashSet::new();
+    let mut non_allergen: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for (ingredients, allergens) in foods {
+        for allergen in allergens {
+            non_allergen.entry(allergen.clone()).or_default();
+        }
+
+        all_ingredients.extend(ingredients.iter().cloned());
+    }
+
+    for (ingredients, allergens) in foods {
+        // all other ingredients cannot contain these allergens
+        for ingredient in all_ingredients.difference(ingredients) {
+            for allergen in allergens {
+                non_allergen
+                    .get_mut(allergen)
+                    .unwrap()
+                    .insert(ingredient.clone());
+            }
+        }
+    }
+
+    (all_ingredients, non_allergen)
+}
+
+const ALLERGENS: [&str; 8] = [
+    "dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "soy", "wheat",
+];
+
+/// Makes up foods where each allergen is in its own ingredient, trying again
+/// until the allergens can be worked out from the list.
+fn generate_foods(rng: &mut Rng, size: usize) -> String {
+    // fewer foods can only pin down fewer allergens
+    let allergens = &ALLERGENS[..(size / 5).clamp(1, ALLERGENS.len())];
+
+    let mut names = HashSet::new();
+    while names.len() < 200 {
+        let length = rng.range(3..=8);
+        names.insert(
+            (0..length)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect::<String>(),
+        );
+    }
+    let mut names: Vec<String> = names.into_iter().collect();
+    names.sort();
+
+    loop {
+        rng.shuffle(&mut names);
+        let (dangerous, safe) = names.split_at(allergens.len());
+
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut listed = Vec::new();
+            let mut ingredients = Vec::new();
+            for (allergen, ingredient) in allergens.iter().zip(dangerous) {
+                // foods don't always list all their allergens
+                match rng.below(4) {
+                    0 => listed.push(*allergen),
+                    1 => {}
+                    _ => continue,
+                }
+                ingredients.push(ingredient.as_str());
+            }
+            for _ in 0..rng.range(5..=30) {
+                let ingredient = rng.choose(safe).as_str();
+                if !ingredients.contains(&ingredient) {
+                    ingredients.push(ingredient);
+                }
+            }
+            rng.shuffle(&mut ingredients);
+
+            input.push_str(&ingredients.join(" "));
+            if !listed.is_empty() {
+                input.push_str(&format!(" (contains {})", listed.join(", ")));
+            }
+            input.push('\n');
+        }
+
+        let foods = Day21::parse(&input).unwrap();
+        let expected: BTreeMap<String, String> = allergens
+            .iter()
+            .map(|allergen| allergen.to_string())
+            .zip(dangerous.iter().cloned())
+            .collect();
+        if assign_allergens(&foods).ok() == Some(expected) {
+            return input;
+        }
+    }
+}
+
+const EXAMPLE: &str = "\
+mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day21>(Part::BOTH);
+    }
+}
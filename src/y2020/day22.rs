@@ -0,0 +1,292 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse::{self, Paragraph};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Solver};
+use crate::trace;
+
+pub struct Day22;
+
+pub type Deck = VecDeque<u32>;
+
+impl Solver for Day22 {
+    const DAY: u32 = 22;
+    const TITLE: &'static str = "Crab Combat";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("306"),
+        part_two: Some("291"),
+        params: &[],
+    }];
+
+    /// Cards in each player's deck.
+    const INPUT_SIZE: usize = 25;
+
+    type Input = (Deck, Deck);
+
+    fn parse(input: &str) -> Result<(Deck, Deck), Error> {
+        let mut sections = parse::sections(input);
+        let mut seen = HashSet::new();
+        let p1_cards = read_player(sections.named("Player 1:")?, 1, &mut seen)?;
+        let p2_cards = read_player(sections.named("Player 2:")?, 2, &mut seen)?;
+        sections.finish()?;
+
+        print_deck(&p1_cards);
+        print_deck(&p2_cards);
+
+        Ok((p1_cards, p2_cards))
+    }
+
+    fn part_one((p1_cards, p2_cards): &(Deck, Deck), _params: &Params) -> Result<Answer, Error> {
+        Ok(play_game(&mut p1_cards.clone(), &mut p2_cards.clone()).into())
+    }
+
+    fn part_two((p1_cards, p2_cards): &(Deck, Deck), _params: &Params) -> Result<Answer, Error> {
+        let mut p1_cards = p1_cards.clone();
+        let mut p2_cards = p2_cards.clone();
+
+        let score = match play_recursive_game(&mut p1_cards, &mut p2_cards) {
+            Player::Player1 => compute_score(&p1_cards),
+            Player::Player2 => compute_score(&p2_cards),
+        };
+
+        Ok(score.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut cards: Vec<u32> = (1..=2 * size as u32).collect();
+        rng.shuffle(&mut cards);
+
+        let deck =
+            |cards: &[u32]| -> String { cards.iter().map(|card| format!("{}\n", card)).collect() };
+        let (p1_cards, p2_cards) = cards.split_at(size);
+        Some(format!(
+            "Player 1:\n{}\nPlayer 2:\n{}",
+            deck(p1_cards),
+            deck(p2_cards)
+        ))
+    }
+}
+
+/// Reads a player's cards, from their section of the input.
+///
+/// Every card is different, so `seen` holds the cards already dealt.
+fn read_player(section: Paragraph, player: u32, seen: &mut HashSet<u32>) -> Result<Deck, Error> {
+    let cards: Deck = section
+        .records(|line| {
+            let card = line.parse::<u32>(line.text)?;
+            if !seen.insert(card) {
+                return Err(line.error(line.text.trim(), format!("card {} is dealt twice", card)));
+            }
+            Ok(card)
+        })?
+        .into_iter()
+        .collect();
+
+    if cards.is_empty() {
+        let header = section.header.unwrap();
+        let message = format!("player {} has no cards", player);
+        return Err(section.in_section(header.error(header.text, message)));
+    }
+
+    Ok(cards)
+}
+
+pub fn compute_score(deck: &Deck) -> u32 {
+    let mut score = 0;
+    for (i, card) in deck.iter().rev().enumerate() {
+        score += (i as u32 + 1) * card;
+    }
+
+    score
+}
+
+fn print_deck(deck: &Deck) {
+    let cards: Vec<_> = deck.iter().map(|card| card.to_string()).collect();
+    log!("{}", cards.join(", "));
+}
+
+/// Plays a game of Combat, and returns the winner's score.
+pub fn play_game(p1_cards: &mut Deck, p2_cards: &mut Deck) -> u32 {
+    for round in 1.. {
+        if p1_cards.is_empty() || p2_cards.is_empty() {
+            // game ends
+            if p1_cards.is_empty() {
+                return compute_score(p2_cards);
+            } else {
+                return compute_score(p1_cards);
+            }
+        }
+
+        let c1 = p1_cards.pop_front().unwrap();
+        let c2 = p2_cards.pop_front().unwrap();
+
+        let winner = match c1.cmp(&c2) {
+            Ordering::Greater => {
+                // player 1 wins round
+                p1_cards.push_back(c1);
+                p1_cards.push_back(c2);
+                1
+            }
+            Ordering::Less => {
+                // player 2 wins round
+                p2_cards.push_back(c2);
+                p2_cards.push_back(c1);
+                2
+            }
+            Ordering::Equal => panic!("got same card in both decks"),
+        };
+        trace!(
+            "round",
+            game = 1,
+            round = round,
+            p1 = c1,
+            p2 = c2,
+            winner = winner
+        );
+    }
+    unreachable!()
+}
+
+pub enum Player {
+    Player1,
+    Player2,
+}
+
+fn hash_game(p1_cards: &Deck, p2_cards: &Deck) -> String {
+    let mut hash = String::new();
+
+    for card in p1_cards {
+        hash.push_str(format!("{},", card).as_str());
+    }
+    hash.push('|');
+    for card in p2_cards {
+        hash.push_str(format!("{},", card).as_str());
+    }
+
+    hash
+}
+
+pub fn play_recursive_game(p1_cards: &mut Deck, p2_cards: &mut Deck) -> Player {
+    play_subgame(p1_cards, p2_cards, 1, &mut 1)
+}
+
+/// Plays game number `game`, counting the games started in `n_games` so that
+/// each one gets its own number in the trace.
+fn play_subgame(p1_cards: &mut Deck, p2_cards: &mut Deck, game: u32, n_games: &mut u32) -> Player {
+    trace!(
+        "game",
+        game = game,
+        p1 = p1_cards.iter().copied().collect::<Vec<_>>(),
+        p2 = p2_cards.iter().copied().collect::<Vec<_>>()
+    );
+
+    // keep a set of all rounds (as strings) to prevent infinite games
+    let mut rounds = HashSet::new();
+
+    for round in 1.. {
+        let hash = hash_game(p1_cards, p2_cards);
+        if rounds.contains(&hash) {
+            return Player::Player1;
+        }
+
+        rounds.insert(hash);
+
+        match (p1_cards.is_empty(), p2_cards.is_empty()) {
+            (true, false) => return Player::Player2,
+            (false, true) => return Player::Player1,
+            (true, true) => panic!("both players have no cards in their decks"),
+            (false, false) => (),
+        }
+
+        let c1 = p1_cards.pop_front().unwrap();
+        let c2 = p2_cards.pop_front().unwrap();
+
+        // check if we go into a subgame
+        let winner = if (c1 as usize) <= p1_cards.len() && (c2 as usize) <= p2_cards.len() {
+            let mut p1_subdeck: Deck = p1_cards.iter().take(c1 as usize).copied().collect();
+            let mut p2_subdeck: Deck = p2_cards.iter().take(c2 as usize).copied().collect();
+
+            *n_games += 1;
+            let subgame = *n_games;
+            match play_subgame(&mut p1_subdeck, &mut p2_subdeck, subgame, n_games) {
+                Player::Player1 => {
+                    p1_cards.push_back(c1);
+                    p1_cards.push_back(c2);
+                    1
+                }
+                Player::Player2 => {
+                    p2_cards.push_back(c2);
+                    p2_cards.push_back(c1);
+                    2
+                }
+            }
+        } else {
+            // play normally
+            match c1.cmp(&c2) {
+                Ordering::Greater => {
+                    // player 1 wins round
+                    p1_cards.push_back(c1);
+                    p1_cards.push_back(c2);
+                    1
+                }
+                Ordering::Less => {
+                    // player 2 wins round
+                    p2_cards.push_back(c2);
+                    p2_cards.push_back(c1);
+                    2
+                }
+                Ordering::Equal => panic!("got same card in both decks"),
+            }
+        };
+        trace!(
+            "round",
+            game = game,
+            round = round,
+            p1 = c1,
+            p2 = c2,
+            winner = winner
+        );
+    }
+    unreachable!()
+}
+
+const EXAMPLE: &str = "\
+Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day22>(Part::BOTH);
+    }
+
+    #[test]
+    fn recursive_game_ends_on_repeat() {
+        let mut p1_cards: Deck = vec![43, 19].into_iter().collect();
+        let mut p2_cards: Deck = vec![2, 29, 14].into_iter().collect();
+        let winner = play_recursive_game(&mut p1_cards, &mut p2_cards);
+        assert!(matches!(winner, Player::Player1));
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::checkpoint::{Checkpoint, Snapshot};
+use crate::error::Error;
+use crate::log;
+use crate::params::{Param, Params};
+use crate::parse;
+use crate::progress::Progress;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+use crate::trace;
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    const DAY: u32 = 23;
+    const TITLE: &'static str = "Crab Cups";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("67384529"),
+        part_two: Some("149245887792"),
+        params: &[],
+    }];
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "moves",
+            part: Some(Part::One),
+            default: "100",
+            help: "how many moves the crab makes",
+        },
+        Param {
+            name: "cups",
+            part: Some(Part::One),
+            default: "9",
+            help: "how many cups there are, at least 9",
+        },
+        Param {
+            name: "moves",
+            part: Some(Part::Two),
+            default: "10000000",
+            help: "how many moves the crab makes",
+        },
+        Param {
+            name: "cups",
+            part: Some(Part::Two),
+            default: "1000000",
+            help: "how many cups there are, at least 9",
+        },
+    ];
+
+    /// The number of cups, which is always 9.
+    const INPUT_SIZE: usize = 9;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        let line = match parse::lines(input).next() {
+            Some(line) => line,
+            None => return Err(parse::end_of_input(input, "expected the cup labels")),
+        };
+
+        // the cups must be labelled 1 to 9, once each
+        let start = line.text.trim();
+        let mut seen = [false; 10];
+        for (idx, c) in start.char_indices() {
+            let cup = &start[idx..idx + c.len_utf8()];
+            match c.to_digit(10) {
+                Some(label) if label >= 1 && !seen[label as usize] => seen[label as usize] = true,
+                Some(label) if label >= 1 => {
+                    return Err(line.error(cup, format!("cup {} appears twice", label)))
+                }
+                _ => return Err(line.error(cup, "expected a cup label from 1 to 9")),
+            }
+        }
+        if start.len() != 9 {
+            return Err(line.error(start, "expected the labels of all 9 cups"));
+        }
+
+        log!("initial configuration: {}", start);
+        Ok(start.to_string())
+    }
+
+    fn part_one(start: &String, params: &Params) -> Result<Answer, Error> {
+        let n_cups = n_cups(params)?;
+        simple_game(start, n_cups, params.get("moves")?).map(Answer::from)
+    }
+
+    fn part_two(start: &String, params: &Params) -> Result<Answer, Error> {
+        let n_cups = n_cups(params)?;
+        complex_game(start, n_cups, params.get("moves")?).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, _size: usize) -> Option<String> {
+        let mut labels: Vec<char> = ('1'..='9').collect();
+        rng.shuffle(&mut labels);
+        Some(labels.into_iter().collect::<String>() + "\n")
+    }
+}
+
+/// The number of cups, which has to be enough for the labelled ones.
+fn n_cups(params: &Params) -> Result<usize, Error> {
+    match params.get("cups")? {
+        n_cups if n_cups >= 9 => Ok(n_cups),
+        n_cups => Err(Error::InvalidParam(format!(
+            "need at least 9 cups, not {}",
+            n_cups
+        ))),
+    }
+}
+
+fn print_config(label: &str, cups: &VecDeque<u64>) {
+    let s: Vec<_> = cups.iter().map(|c| format!("{}", c)).collect();
+    log!("{} configuration: {}", label, s.join(","));
+}
+
+pub fn make_move(cups: &mut VecDeque<u64>, max_val: u64) {
+    let cup = cups[0];
+
+    cups.rotate_left(1);
+
+    let c1 = cups.pop_front().unwrap();
+    let c2 = cups.pop_front().unwrap();
+    let c3 = cups.pop_front().unwrap();
+
+    // find next
+    let mut next = if cup == 1 { max_val } else { cup - 1 };
+    while next == c1 || next == c2 || next == c3 {
+        next = if next == 1 { max_val } else { next - 1 };
+    }
+    trace!(
+        "move",
+        current = cup,
+        picked = vec![c1, c2, c3],
+        destination = next
+    );
+
+    let mut insert_idx = 0;
+    for (i, c) in cups.iter().enumerate() {
+        if *c == next {
+            insert_idx = i + 1;
+            break;
+        }
+    }
+
+    cups.insert(insert_idx, c3);
+    cups.insert(insert_idx, c2);
+    cups.insert(insert_idx, c1);
+}
+
+pub fn make_move_map(map: &mut HashMap<u64, u64>, curr: u64, max_val: u64) -> u64 {
+    let c1 = map[&curr];
+    let c2 = map[&c1];
+    let c3 = map[&c2];
+
+    let c4 = map[&c3];
+
+    // remove
+    map.insert(curr, c4);
+
+    // find next
+    let mut next = if curr == 1 { max_val } else { curr - 1 };
+    while next == c1 || next == c2 || next == c3 {
+        next = if next == 1 { max_val } else { next - 1 };
+    }
+    trace!(
+        "move",
+        current = curr,
+        picked = vec![c1, c2, c3],
+        destination = next
+    );
+
+    // insert
+    let other = map[&next];
+
+    map.insert(next, c1);
+    map.insert(c3, other);
+
+    c4
+}
+
+/// store the cups in a simple VecDeque.
+/// this is slower, but good enough for the simple game.
+/// returns the labels of the cups after cup 1, unless it's cancelled first.
+pub fn simple_game(start: &str, n_cups: usize, n_moves: usize) -> Result<String, Error> {
+    let mut cups: VecDeque<u64> = start
+        .chars()
+        .map(|c| c.to_string().parse::<u64>().unwrap())
+        .collect();
+    cups.extend(10..=n_cups as u64);
+
+    let mut progress = Progress::new("move", 0, n_moves);
+    for moves in 1..=n_moves {
+        make_move(&mut cups, n_cups as u64);
+        progress.update(moves)?;
+    }
+    print_config("final", &cups);
+
+    let one_idx = cups.iter().position(|c| *c == 1).unwrap();
+    cups.rotate_left(one_idx);
+    Ok(cups.iter().skip(1).map(|c| c.to_string()).collect())
+}
+
+/// The cups of the complex game partway through.
+#[derive(Debug, PartialEq)]
+struct Circle {
+    /// The cup after each cup.
+    next: HashMap<u64, u64>,
+    current: u64,
+    moves: usize,
+}
+
+impl Circle {
+    fn new(start: &str, max_val: usize) -> Circle {
+        let mut cups: Vec<u64> = (1..=max_val as u64).collect();
+        for (i, c) in start.chars().enumerate() {
+            cups[i] = c.to_string().parse::<u64>().unwrap();
+        }
+
+        assert_eq!(cups.len(), max_val);
+
+        let mut map = HashMap::new();
+        for (i, c) in cups.iter().enumerate() {
+            map.insert(*c, cups[(i + 1) % max_val]);
+        }
+
+        assert_eq!(map.len(), max_val);
+
+        Circle {
+            next: map,
+            current: cups[0],
+            moves: 0,
+        }
+    }
+
+    /// Whether a loaded circle could be one of the cups labelled 1 to
+    /// `max_val` partway through `n_moves`, checked before trusting it with
+    /// any: following the cups from the current one has to go round all of
+    /// them before getting back to it.
+    fn fits(&self, max_val: usize, n_moves: usize) -> bool {
+        let labels = 1..=max_val as u64;
+        if self.moves > n_moves
+            || self.next.len() != max_val
+            || !self.next.keys().all(|cup| labels.contains(cup))
+        {
+            return false;
+        }
+
+        let mut cup = self.current;
+        for step in 1..=max_val {
+            cup = match self.next.get(&cup) {
+                Some(next) => *next,
+                None => return false,
+            };
+            if cup == self.current {
+                return step == max_val;
+            }
+        }
+        false
+    }
+}
+
+impl Snapshot for Circle {
+    fn to_words(&self) -> Vec<u64> {
+        let mut words = vec![self.current, self.moves as u64];
+        for (cup, next) in &self.next {
+            words.extend_from_slice(&[*cup, *next]);
+        }
+        words
+    }
+
+    fn from_words(words: &[u64]) -> Option<Circle> {
+        if words.len() < 2 || !words.len().is_multiple_of(2) {
+            return None;
+        }
+        Some(Circle {
+            next: words[2..]
+                .chunks(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect(),
+            current: words[0],
+            moves: words[1] as usize,
+        })
+    }
+}
+
+/// store the cups in a singly-linked circular-list hash map.
+/// then moving the cups just involves modifying the `next`
+/// reference.
+///
+/// This probably isn't the fastest method: still takes about
+/// a minute to run in debug mode (<10s in release mode).
+pub fn complex_game(start: &str, max_val: usize, n_moves: usize) -> Result<u64, Error> {
+    let key = format!("{} cups={} moves={}", start, max_val, n_moves);
+    let mut checkpoint = Checkpoint::new("day23", key);
+    let mut circle = checkpoint
+        .as_ref()
+        .and_then(|checkpoint| checkpoint.load(|circle: &Circle| circle.fits(max_val, n_moves)))
+        .unwrap_or_else(|| Circle::new(start, max_val));
+
+    let mut progress = Progress::new("move", circle.moves, n_moves);
+    while circle.moves < n_moves {
+        circle.current = make_move_map(&mut circle.next, circle.current, max_val as u64);
+        circle.moves += 1;
+        progress.update(circle.moves)?;
+        if let Some(checkpoint) = &mut checkpoint {
+            if checkpoint.due(circle.moves) {
+                checkpoint.save(&circle);
+            }
+        }
+    }
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish();
+    }
+
+    // find cups after 1.
+    let first = circle.next.get(&1).unwrap();
+    let second = circle.next.get(first).unwrap();
+
+    log!("cups after 1: {} {}", first, second);
+    Ok(first * second)
+}
+
+const EXAMPLE: &str = "\
+389125467
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::{check_agreement, Rng};
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day23>(&[Part::One]);
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn examples_part_two() {
+        check_examples::<Day23>(&[Part::Two]);
+    }
+
+    #[test]
+    fn moves_agree() {
+        // some cups in order, and how many moves to make
+        let generate = |rng: &mut Rng, size| {
+            let mut cups: Vec<u64> = (1..=5 + size as u64).collect();
+            rng.shuffle(&mut cups);
+            (cups, rng.below(10 * size))
+        };
+        // the cups after 1, and the current cup
+        let with_deque = |(cups, n_moves): &(Vec<u64>, usize)| {
+            let max_val = cups.len() as u64;
+            let mut cups: VecDeque<u64> = cups.iter().copied().collect();
+            for _ in 0..*n_moves {
+                make_move(&mut cups, max_val);
+            }
+            let current = cups[0];
+            let one_idx = cups.iter().position(|c| *c == 1).unwrap();
+            cups.rotate_left(one_idx);
+            (cups.into_iter().skip(1).collect::<Vec<_>>(), current)
+        };
+        let with_map = |(cups, n_moves): &(Vec<u64>, usize)| {
+            let mut map = HashMap::new();
+            for (idx, cup) in cups.iter().enumerate() {
+                map.insert(*cup, cups[(idx + 1) % cups.len()]);
+            }
+            let mut current = cups[0];
+            for _ in 0..*n_moves {
+                current = make_move_map(&mut map, current, cups.len() as u64);
+            }
+            let after_one = std::iter::successors(Some(map[&1]), |cup| Some(map[cup]));
+            (after_one.take(cups.len() - 1).collect(), current)
+        };
+        check_agreement(300, 50, generate, with_deque, with_map);
+    }
+
+    #[test]
+    fn fewer_cups_and_moves() {
+        // after 100 moves the cups following 1 are 6 and 7, as in part one
+        let mut params = Params::new();
+        params.set("cups", "9");
+        params.set("moves", "100");
+        let answer = Day23::solve_with(EXAMPLE, Part::Two, &params);
+        assert_eq!(answer, Ok(42.into()));
+    }
+
+    #[test]
+    fn resumes_from_snapshot() {
+        let mut circle = Circle::new("389125467", 20);
+        let mut resumed = Circle::from_words(&circle.to_words()).unwrap();
+        assert_eq!(resumed, circle);
+
+        for _ in 0..50 {
+            circle.current = make_move_map(&mut circle.next, circle.current, 20);
+            resumed.current = make_move_map(&mut resumed.next, resumed.current, 20);
+        }
+        assert_eq!(resumed, circle);
+    }
+
+    #[test]
+    fn rejects_corrupt_snapshots() {
+        let circle = Circle::new("389125467", 20);
+        assert!(circle.fits(20, 100));
+        assert!(!circle.fits(21, 100));
+
+        let corrupt = |change: &dyn Fn(&mut Circle)| {
+            let mut circle = Circle::from_words(&circle.to_words()).unwrap();
+            change(&mut circle);
+            circle.fits(20, 100)
+        };
+        assert!(!corrupt(&|circle| circle.moves = 101));
+        assert!(!corrupt(&|circle| circle.current = 21));
+        // two circles of cups
+        assert!(!corrupt(&|circle| {
+            circle.next.insert(3, 3);
+            circle.next.insert(7, 8);
+        }));
+        // a cup that isn't one of the labels
+        assert!(!corrupt(&|circle| {
+            let next = circle.next.remove(&20).unwrap();
+            circle.next.insert(0, next);
+        }));
+    }
+
+    #[test]
+    fn ten_moves() {
+        let mut cups: VecDeque<u64> = vec![3, 8, 9, 1, 2, 5, 4, 6, 7].into_iter().collect();
+        for _ in 0..10 {
+            make_move(&mut cups, 9);
+        }
+        let one_idx = cups.iter().position(|c| *c == 1).unwrap();
+        cups.rotate_left(one_idx);
+        let labels: String = cups.iter().skip(1).map(|c| c.to_string()).collect();
+        assert_eq!(labels, "92658374");
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use crate::automaton::{Automaton, Hex, Rule};
+use crate::error::Error;
+use crate::params::{Param, Params};
+use crate::parse::{self, Line};
+use crate::render::{self, Frame};
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    const DAY: u32 = 24;
+    const TITLE: &'static str = "Lobby Layout";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("10"),
+        part_two: Some("2208"),
+        params: &[],
+    }];
+    const PARAMS: &'static [Param] = &[Param {
+        name: "days",
+        part: Some(Part::Two),
+        default: "100",
+        help: "how many days the tiles are flipped for",
+    }];
+
+    /// Lines of directions to tiles.
+    const INPUT_SIZE: usize = 320;
+
+    type Input = HashSet<(i32, i32)>;
+
+    fn parse(input: &str) -> Result<HashSet<(i32, i32)>, Error> {
+        let mut black_tiles = HashSet::new();
+
+        for line in parse::lines(input) {
+            let coords = get_coords(line)?;
+
+            if black_tiles.contains(&coords) {
+                black_tiles.remove(&coords);
+            } else {
+                black_tiles.insert(coords);
+            }
+        }
+
+        Ok(black_tiles)
+    }
+
+    fn check(input: &str) -> Vec<Error> {
+        parse::line_errors(input, get_coords)
+    }
+
+    fn part_one(black_tiles: &HashSet<(i32, i32)>, _params: &Params) -> Result<Answer, Error> {
+        Ok(black_tiles.len().into())
+    }
+
+    fn part_two(black_tiles: &HashSet<(i32, i32)>, params: &Params) -> Result<Answer, Error> {
+        let mut floor = lobby(black_tiles);
+        floor.run(params.get("days")?);
+
+        Ok(floor.population().into())
+    }
+
+    fn frames(
+        black_tiles: &HashSet<(i32, i32)>,
+        part: Part,
+        params: &Params,
+    ) -> Result<Vec<Frame>, Error> {
+        let history = match part {
+            Part::One => vec![black_tiles.clone()],
+            Part::Two => lobby(black_tiles).history(Some(params.get("days")?)),
+        };
+        Ok(render::hex_frames(&history))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const DIRECTIONS: [&str; 6] = ["e", "se", "sw", "w", "nw", "ne"];
+
+        let mut lines: Vec<Vec<&str>> = Vec::new();
+        for _ in 0..size {
+            let line = match lines.is_empty() || !rng.chance(0.1) {
+                true => (0..rng.range(15..=25))
+                    .map(|_| *rng.choose(&DIRECTIONS))
+                    .collect(),
+                false => {
+                    // another way to the same tile, to flip it back
+                    let mut line = rng.choose(&lines).clone();
+                    rng.shuffle(&mut line);
+                    line
+                }
+            };
+            lines.push(line);
+        }
+
+        Some(lines.into_iter().map(|line| line.concat() + "\n").collect())
+    }
+}
+
+/// The tiles of the lobby floor, flipped each day.
+fn lobby(black_tiles: &HashSet<(i32, i32)>) -> Automaton<Hex> {
+    // black tiles with 1 or 2 black neighbours stay black, and white
+    // tiles with 2 turn black
+    let rule = Rule::new(2..=2, 1..=2);
+    Automaton::new(Hex, rule, black_tiles.iter().copied())
+}
+
+/// Follows the directions on the line from the reference tile.
+pub fn get_coords(line: Line) -> Result<(i32, i32), Error> {
+    let path = line.text.trim();
+
+    let mut x = 0;
+    let mut y = 0;
+
+    let mut chars = path.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        let bad_direction = |len: usize| {
+            let direction = &path[idx..(idx + len).min(path.len())];
+            line.error(
+                direction,
+                "expected one of `e`, `se`, `sw`, `w`, `nw` or `ne`",
+            )
+        };
+
+        match c {
+            'e' => x += 1,
+            'w' => x -= 1,
+            's' => {
+                y -= 1;
+                match chars.next() {
+                    Some((_, 'e')) => x += 1,
+                    Some((_, 'w')) => (),
+                    _ => return Err(bad_direction(2)),
+                }
+            }
+            'n' => {
+                y += 1;
+                match chars.next() {
+                    Some((_, 'e')) => (),
+                    Some((_, 'w')) => x -= 1,
+                    _ => return Err(bad_direction(2)),
+                }
+            }
+            _ => return Err(bad_direction(c.len_utf8())),
+        }
+    }
+
+    Ok((x, y))
+}
+
+const EXAMPLE: &str = "\
+sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::check_examples;
+
+    #[test]
+    fn examples() {
+        check_examples::<Day24>(Part::BOTH);
+    }
+
+    #[test]
+    fn paths() {
+        let coords = |text| get_coords(Line { number: 1, text }).unwrap();
+        assert_eq!(coords("esew"), (1, -1));
+        assert_eq!(coords("nwwswee"), (0, 0));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::error::Error;
+use crate::log;
+use crate::params::Params;
+use crate::parse;
+use crate::rng::Rng;
+use crate::solver::{Answer, Example, Part, Solver};
+
+pub struct Day25;
+
+const MODULO: u64 = 20201227;
+
+impl Solver for Day25 {
+    const DAY: u32 = 25;
+    const TITLE: &'static str = "Combo Breaker";
+    const PARTS: &'static [Part] = &[Part::One];
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("14897079"),
+        part_two: None,
+        params: &[],
+    }];
+
+    /// The largest loop size, which is at most the modulus.
+    const INPUT_SIZE: usize = 10_000_000;
+
+    type Input = (u64, u64);
+
+    fn parse(input: &str) -> Result<(u64, u64), Error> {
+        let mut keys = Vec::new();
+        for line in parse::lines(input).filter(|line| !line.text.trim().is_empty()) {
+            let key = line.parse::<u64>(line.text)?;
+            if key == 0 || key >= MODULO {
+                let message = format!("public keys are between 1 and {}", MODULO - 1);
+                return Err(line.error(line.text.trim(), message));
+            }
+            if keys.len() == 2 {
+                return Err(line.error(line.text, "expected only two public keys"));
+            }
+            keys.push(key);
+        }
+
+        match keys[..] {
+            [key1, key2] => Ok((key1, key2)),
+            _ => Err(parse::end_of_input(input, "expected two public keys")),
+        }
+    }
+
+    fn part_one(&(key1, key2): &(u64, u64), _params: &Params) -> Result<Answer, Error> {
+        log!("public keys: {} {}", key1, key2);
+
+        let loop_size1 = get_loop_size(7, key1);
+        let loop_size2 = get_loop_size(7, key2);
+
+        log!("loop sizes: {} {}", loop_size1, loop_size2);
+
+        let enc_key1 = transform(key2, loop_size1);
+        let enc_key2 = transform(key1, loop_size2);
+
+        if enc_key1 != enc_key2 {
+            return Err(Error::NoSolution(format!(
+                "the encryption keys don't agree ({} and {})",
+                enc_key1, enc_key2
+            )));
+        }
+        Ok(enc_key1.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let max_loop_size = size.clamp(1, MODULO as usize - 1) as i64;
+        let mut key = || transform(7, rng.range(1..=max_loop_size) as u64);
+        Some(format!("{}\n{}\n", key(), key()))
+    }
+}
+
+/// Given a subject number and a resulting key, find
+/// the loop size used to obtain the key.
+pub fn get_loop_size(subject: u64, key: u64) -> u64 {
+    let mut value = 1u64;
+    let mut iter = 0u64;
+
+    while value != key {
+        value *= subject;
+        value %= MODULO;
+        iter += 1;
+
+        // println!("after {} iterations, value is {}", iter, value);
+    }
+
+    iter
+}
+
+/// Given a subject number and a loop size, compute the
+/// resulting key.
+pub fn transform(subject: u64, loop_size: u64) -> u64 {
+    let mut value = 1u64;
+
+    for _ in 0..loop_size {
+        value *= subject;
+        value %= MODULO;
+    }
+
+    value
+}
+
+const EXAMPLE: &str = "\
+5764801
+17807724
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{check_examples, Part};
+
+    #[test]
+    fn examples() {
+        check_examples::<Day25>(Part::BOTH);
+    }
+
+    #[test]
+    fn loop_sizes() {
+        assert_eq!(get_loop_size(7, 5764801), 8);
+        assert_eq!(get_loop_size(7, 17807724), 11);
+        assert_eq!(transform(17807724, 8), 14897079);
+        assert_eq!(transform(5764801, 11), 14897079);
+    }
+}
@@ -0,0 +1,41 @@
+//! Runs the `all` command the way it's run from the command line.
+
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn reports_other_days_after_a_bad_one() {
+    let dir = std::env::temp_dir().join(format!("aoc-all-{}", std::process::id()));
+    let year = dir.join("2020");
+    fs::create_dir_all(&year).unwrap();
+    fs::write(year.join("day01.txt"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+    fs::write(year.join("day02.txt"), "1-3 a: abcde\nnonsense\n").unwrap();
+    fs::write(year.join("day25.txt"), "5764801\n17807724\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc-2020-rust"))
+        .args(["all", "--no-cache", "--jobs", "2", "--inputs"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row = |day: u32, part: u32| {
+        stdout
+            .lines()
+            .find(|line| {
+                line.split_whitespace()
+                    .take(2)
+                    .eq([day, part].iter().map(u32::to_string))
+            })
+            .unwrap_or_else(|| panic!("no row for day {} part {}:\n{}", day, part, stdout))
+    };
+    assert!(row(1, 1).contains("ok") && row(1, 1).ends_with("514579"));
+    assert!(row(1, 2).contains("ok") && row(1, 2).ends_with("241861950"));
+    assert!(row(2, 1).contains("error") && row(2, 1).contains("line 2, column 1"));
+    assert!(row(2, 2).contains("error"));
+    assert!(row(3, 1).contains("no input"));
+    assert!(row(25, 1).contains("ok") && row(25, 1).ends_with("14897079"));
+    assert!(stdout.contains("2 failed"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(1));
+}